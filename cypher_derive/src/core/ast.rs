@@ -3,11 +3,8 @@ use syn::punctuated::Punctuated;
 use super::{attr, context::Context};
 
 pub struct Container<'a> {
-    pub ident: syn::Ident,
     pub attrs: attr::Container,
     pub data: (Style, Vec<Field<'a>>),
-    pub generics: &'a syn::Generics,
-    pub original: &'a syn::DeriveInput,
 }
 
 pub enum Style {
@@ -15,9 +12,7 @@ pub enum Style {
 }
 
 pub struct Field<'a> {
-    pub member: syn::Member,
     pub attrs: attr::Field,
-    pub ty: &'a syn::Type,
     pub original: &'a syn::Field,
 }
 
@@ -31,13 +26,7 @@ impl<'a> Container<'a> {
             syn::Data::Union(_) => todo!(),
         };
 
-        let item = Container {
-            ident: input.ident.clone(),
-            attrs,
-            data,
-            generics: &input.generics,
-            original: input,
-        };
+        let item = Container { attrs, data };
 
         Some(item)
     }
@@ -64,12 +53,7 @@ fn fields_from_ast<'a>(
         .iter()
         .enumerate()
         .map(|(i, field)| Field {
-            member: match &field.ident {
-                Some(ident) => syn::Member::Named(ident.clone()),
-                None => syn::Member::Unnamed(i.into()),
-            },
             attrs: attr::Field::from_ast(ctx, i, field, attrs),
-            ty: &field.ty,
             original: field,
         })
         .collect()
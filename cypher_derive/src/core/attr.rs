@@ -10,12 +10,27 @@ use super::context::Context;
 use super::respan::*;
 use super::symbol::*;
 
+#[allow(clippy::enum_variant_names)]
 pub enum Default {
     None,
     Default,
     Value(String),
 }
 
+/// How a field holding another `CypQue` struct is written.
+pub enum Nested {
+    None,
+    /// Fields of the nested struct become properties of the parent, with a prefix.
+    Flatten(String),
+    /// The nested struct is written as a single map property.
+    ///
+    /// Neo4j can not store a map as a property value, so a node with such a field
+    /// can not be created or merged as is; the map is meant for `SET n += $map`.
+    Map,
+    /// The nested struct is written as a single JSON string property.
+    Json,
+}
+
 pub struct Attr<'a, T> {
     ctx: &'a Context,
     name: Symbol,
@@ -86,7 +101,10 @@ pub struct Field {
     pub name: Name,
     pub skip: bool,
     pub label: bool,
+    pub key: bool,
     pub default: Default,
+    pub nested: Nested,
+    pub temporal: bool,
 }
 
 impl Field {
@@ -97,11 +115,15 @@ impl Field {
         _attrs: Option<&Variant>,
     ) -> Self {
         let mut set_name = Attr::none(ctx, RENAME);
-        let get_name = Attr::none(ctx, RENAME);
 
         let mut skip = BoolAttr::none(ctx, SKIP);
         let mut label = BoolAttr::none(ctx, LABEL);
+        let mut key = BoolAttr::none(ctx, KEY);
         let mut default = Attr::none(ctx, DEFAULT);
+        let mut flatten = Attr::none(ctx, FLATTEN);
+        let mut map = BoolAttr::none(ctx, MAP);
+        let mut json = BoolAttr::none(ctx, JSON);
+        let mut temporal = BoolAttr::none(ctx, TEMPORAL);
 
         let ident = match &field.ident {
             Some(ident) => unraw(ident),
@@ -117,7 +139,7 @@ impl Field {
             match &meta_item {
                 // Parse `#[cypher(rename = "name")]`
                 Meta(NameValue(m)) if m.path == RENAME => {
-                    if let Ok(s) = get_lit_str(ctx, RENAME, &m.lit) {
+                    if let Ok(s) = get_ident_str(ctx, RENAME, &m.lit) {
                         set_name.set(&m.path, s.value());
                     }
                 }
@@ -128,6 +150,9 @@ impl Field {
                 // Parse `#[cypher(label)]`
                 Meta(Path(word)) if word == LABEL => label.set_true(word),
 
+                // Parse `#[cypher(key)]`
+                Meta(Path(word)) if word == KEY => key.set_true(word),
+
                 // Parse `#[cypher(default)]`
                 Meta(Path(word)) if word == DEFAULT => default.set(word, Default::Default),
 
@@ -138,6 +163,25 @@ impl Field {
                     }
                 }
 
+                // Parse `#[cypher(flatten)]`
+                Meta(Path(word)) if word == FLATTEN => flatten.set(word, String::new()),
+
+                // Parse `#[cypher(flatten = "prefix_")]`
+                Meta(NameValue(m)) if m.path == FLATTEN => {
+                    if let Ok(s) = get_lit_str(ctx, FLATTEN, &m.lit) {
+                        flatten.set(&m.path, s.value());
+                    }
+                }
+
+                // Parse `#[cypher(map)]`
+                Meta(Path(word)) if word == MAP => map.set_true(word),
+
+                // Parse `#[cypher(json)]`
+                Meta(Path(word)) if word == JSON => json.set_true(word),
+
+                // Parse `#[cypher(temporal)]`
+                Meta(Path(word)) if word == TEMPORAL => temporal.set_true(word),
+
                 Meta(meta_item) => {
                     let path = meta_item
                         .path()
@@ -156,34 +200,66 @@ impl Field {
             }
         }
 
+        // Key fields are written as properties, so they can not be hidden or used as labels
+        if key.get() && (skip.get() || label.get()) {
+            ctx.error_spanned_by(
+                field,
+                "cypher `key` attribute can not be combined with `skip` or `label`",
+            );
+        }
+
+        let default = default.get().unwrap_or(Default::None);
+        let nested = match (flatten.get(), map.get(), json.get()) {
+            (None, false, false) => Nested::None,
+            (Some(prefix), false, false) => Nested::Flatten(prefix),
+            (None, true, false) => Nested::Map,
+            (None, false, true) => Nested::Json,
+            _ => {
+                ctx.error_spanned_by(
+                    field,
+                    "only one of cypher `flatten`, `map` and `json` attributes can be used",
+                );
+                Nested::None
+            }
+        };
+
+        // Nested structs are written as a whole, so they can not be keys, labels or have defaults
+        let has_default = !matches!(default, Default::None);
+        if !matches!(nested, Nested::None) && (key.get() || label.get() || has_default) {
+            ctx.error_spanned_by(
+                field,
+                "cypher `flatten`, `map` and `json` attributes can not be combined with `key`, `label` or `default`",
+            );
+        }
+
+        // Temporal values are properties, not labels or nested structs
+        if temporal.get() && (label.get() || !matches!(nested, Nested::None)) {
+            ctx.error_spanned_by(
+                field,
+                "cypher `temporal` attribute can not be combined with `label`, `flatten`, `map` or `json`",
+            );
+        }
+
         Field {
-            name: Name::from_attrs(ident, set_name, get_name),
+            name: Name::from_attrs(ident, set_name),
             skip: skip.get(),
             label: label.get(),
-            default: default.get().unwrap_or(Default::None),
+            key: key.get(),
+            default,
+            nested,
+            temporal: temporal.get(),
         }
     }
 }
 
 pub struct Name {
     pub settable: String,
-    pub settable_renamed: bool,
-    pub gettable: String,
-    pub gettable_renamed: bool,
 }
 
 impl Name {
-    fn from_attrs(source_name: String, set_name: Attr<String>, get_name: Attr<String>) -> Name {
-        let set_name = set_name.get();
-        let set_renamed = set_name.is_some();
-        let get_name = get_name.get();
-        let get_renamed = get_name.is_some();
-
+    fn from_attrs(source_name: String, set_name: Attr<String>) -> Name {
         Name {
-            settable: set_name.unwrap_or_else(|| source_name.clone()),
-            settable_renamed: set_renamed,
-            gettable: get_name.unwrap_or(source_name),
-            gettable_renamed: get_renamed,
+            settable: set_name.get().unwrap_or(source_name),
         }
     }
 }
@@ -197,7 +273,6 @@ impl Container {
     /// Извлечение атрибутов элемента
     pub fn from_ast(ctx: &Context, input: &DeriveInput) -> Self {
         let mut set_name = Attr::none(ctx, RENAME);
-        let get_name = Attr::none(ctx, RENAME);
 
         for meta_input in input
             .attrs
@@ -207,7 +282,7 @@ impl Container {
         {
             match &meta_input {
                 Meta(NameValue(m)) if m.path == RENAME => {
-                    if let Ok(s) = get_lit_str(ctx, RENAME, &m.lit) {
+                    if let Ok(s) = get_ident_str(ctx, RENAME, &m.lit) {
                         set_name.set(&m.path, s.value());
                     }
                 }
@@ -231,7 +306,7 @@ impl Container {
         }
 
         Container {
-            name: Name::from_attrs(unraw(&input.ident), set_name, get_name),
+            name: Name::from_attrs(unraw(&input.ident), set_name),
         }
     }
 }
@@ -244,7 +319,6 @@ pub struct Variant {
 impl<'a> Variant {
     #[allow(dead_code)]
     pub fn from_ast(ctx: &'a Context, variant: &'a syn::Variant) -> Self {
-        let get_name = Attr::none(ctx, RENAME);
         let mut set_name = Attr::none(ctx, RENAME);
 
         for meta_item in variant
@@ -255,7 +329,7 @@ impl<'a> Variant {
         {
             match &meta_item {
                 Meta(NameValue(m)) if m.path == RENAME => {
-                    if let Ok(s) = get_lit_str(ctx, RENAME, &m.lit) {
+                    if let Ok(s) = get_ident_str(ctx, RENAME, &m.lit) {
                         set_name.set(&m.path, s.value());
                     }
                 }
@@ -279,7 +353,7 @@ impl<'a> Variant {
         }
 
         Variant {
-            name: Name::from_attrs(unraw(&variant.ident), set_name, get_name),
+            name: Name::from_attrs(unraw(&variant.ident), set_name),
         }
     }
 }
@@ -308,6 +382,34 @@ fn get_lit_str<'a>(
     }
 }
 
+/// Same as `get_lit_str`, but also checks that the string can be used
+/// as a name of a label, relationship type or property key.
+fn get_ident_str<'a>(
+    ctx: &Context,
+    attr_name: Symbol,
+    lit: &'a syn::Lit,
+) -> Result<&'a syn::LitStr, ()> {
+    let string = get_lit_str(ctx, attr_name, lit)?;
+    let value = string.value();
+
+    if value.is_empty() {
+        ctx.error_spanned_by(lit, format!("cypher {} attribute can not be empty", attr_name));
+        return Err(());
+    }
+    if value.contains('\0') {
+        ctx.error_spanned_by(
+            lit,
+            format!(
+                "cypher {} attribute can not contain a null character",
+                attr_name
+            ),
+        );
+        return Err(());
+    }
+
+    Ok(string)
+}
+
 fn get_cypher_meta_inputs(
     ctx: &Context,
     attr: &syn::Attribute,
@@ -8,6 +8,11 @@ pub const RENAME: Symbol = Symbol("rename");
 pub const SKIP: Symbol = Symbol("skip");
 pub const LABEL: Symbol = Symbol("label");
 pub const DEFAULT: Symbol = Symbol("default");
+pub const KEY: Symbol = Symbol("key");
+pub const FLATTEN: Symbol = Symbol("flatten");
+pub const MAP: Symbol = Symbol("map");
+pub const JSON: Symbol = Symbol("json");
+pub const TEMPORAL: Symbol = Symbol("temporal");
 
 impl PartialEq<Symbol> for Ident {
     fn eq(&self, word: &Symbol) -> bool {
@@ -15,7 +20,7 @@ impl PartialEq<Symbol> for Ident {
     }
 }
 
-impl<'a> PartialEq<Symbol> for &'a Ident {
+impl PartialEq<Symbol> for &Ident {
     fn eq(&self, word: &Symbol) -> bool {
         *self == word.0
     }
@@ -27,7 +32,7 @@ impl PartialEq<Symbol> for Path {
     }
 }
 
-impl<'a> PartialEq<Symbol> for &'a Path {
+impl PartialEq<Symbol> for &Path {
     fn eq(&self, word: &Symbol) -> bool {
         self.is_ident(word.0)
     }
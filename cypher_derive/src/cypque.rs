@@ -12,6 +12,7 @@ pub fn expand_derive_cypque(input: &mut syn::DeriveInput) -> Result<TokenStream,
 
     let props = collect_props(&cont)?;
     let labels = collect_labels(&cont)?;
+    let keys = collect_keys(&cont)?;
 
     let node_query_name = &cont.attrs.name.settable;
     let node_ident_name = &input.ident;
@@ -23,20 +24,28 @@ pub fn expand_derive_cypque(input: &mut syn::DeriveInput) -> Result<TokenStream,
 
         impl #impl_generics NodeTrait for #node_ident_name #ty_generics #where_clause{
             fn node(&self, nv: &str)-> Node {
+                self.try_node(nv).unwrap_or_else(|err| panic!("{}", err))
+            }
+
+            fn try_node(&self, nv: &str) -> Result<Node, neo4j_cypher::entity::IntRangeError> {
                 use std::sync::Arc;
 
                 let mut mp = Props::new();
-                #(mp.insert(#props);)*
+                #(#props)*
 
                 let mut lb: Vec<Box<dyn Display>> = Vec::new();
                 #(lb.push(#labels);)*
 
-                Node::new(
+                Ok(Node::new(
                     nv.clone().to_string(), 
                     (&#node_query_name), 
                     if mp.len() > 0 { Some(mp) } else { None }, 
                     if lb.len() > 0 { Some(lb) } else { None },
-                )                             
+                ))
+            }
+
+            fn keys(&self) -> Vec<&'static str> {
+                vec![#(#keys),*]
             }
         }
     );
@@ -59,6 +68,21 @@ fn collect_labels(cont: &ast::Container) -> Result<Vec<TokenStream>, Vec<syn::Er
     Ok(output)
 }
 
+/// Получение имён всех полей которые указаны как `key` узла.
+fn collect_keys(cont: &ast::Container) -> Result<Vec<TokenStream>, Vec<syn::Error>> {
+    let output = cont.data
+        .1
+        .iter()
+        .filter(|field| field.attrs.key)
+        .map(|field| {
+            let set_name = field.attrs.name.settable.as_str();
+            quote!(#set_name)
+        })
+        .collect::<Vec<_>>();
+
+    Ok(output)
+}
+
 /// Получение всех полей которые не помечены меткой `label`. 
 /// Все собранные поля и их названия будут использоваться как параметры узла
 /// при формировании запроса.
@@ -68,6 +92,11 @@ fn collect_props(cont: &ast::Container) -> Result<Vec<TokenStream>, Vec<syn::Err
         .iter()
         .filter(|field| !field.attrs.skip && !field.attrs.label)
         .map(|field| {
+            // Поля со вложенной структурой записываются отдельно
+            if let Some(nested) = nested_props(field) {
+                return nested;
+            }
+
             // Нативное имя поля в родительской структуре
             let org_name = field.original.ident.as_ref().unwrap();
             // Имя параметра которое должно быть использовано при формировании запроса
@@ -76,8 +105,11 @@ fn collect_props(cont: &ast::Container) -> Result<Vec<TokenStream>, Vec<syn::Err
             let mut _type = &field.original.ty;
 
             let prop_value =
+                // Временные типы преобразуются через `From<T> for PropType`
+                if let Some(value) = temporal_prop(field) {
+                    value
                 // Проверка, если тип поля опциональным
-                if let Some(type_in_option) = ty_inner_type("Option", &field.original.ty) {
+                } else if let Some(type_in_option) = ty_inner_type("Option", &field.original.ty) {
                     _type = type_in_option;                    
                     
                     // Проверка, если тип поля является массивом
@@ -96,10 +128,10 @@ fn collect_props(cont: &ast::Container) -> Result<Vec<TokenStream>, Vec<syn::Err
                                 PropType::arr(
                                     stringify!(#i_ty), 
                                     self.#org_name.clone().unwrap(),
-                                )                                
+                                )?
                             } else {
-                                // Если будет указано дефолтное значение, тип преобразуется в PropType::StrArr
-                                // и при формировании запроса результат будет идентичен с типом PropType::Array.
+                                // Если будет указано дефолтное значение, оно разбирается
+                                // в массив PropType::Array.
                                 // Если дефолтного значения нет, при формировании запроса 
                                 // будет использоваться тип PropType::Null.
                                 PropType::str_arr(#defval)                         
@@ -123,11 +155,11 @@ fn collect_props(cont: &ast::Container) -> Result<Vec<TokenStream>, Vec<syn::Err
                                 PropType::from_type(
                                     stringify!(#_type), 
                                     Some(Box::new(self.#org_name.clone().unwrap())),
-                                )                                
+                                )?
                             } else {
                                 // Если было определено дефолтное значение, оно будет преобразовано
                                 // в соответствующий тип PropType, иначе будет использоваться тип PropType::Null
-                                PropType::from_type(stringify!(#_type), #defval)                            
+                                PropType::from_type(stringify!(#_type), #defval)?
                             }
                         )
                     }
@@ -135,7 +167,7 @@ fn collect_props(cont: &ast::Container) -> Result<Vec<TokenStream>, Vec<syn::Err
                     // Проверка, если тип поля является массивом
                     if let Some(i_ty) = ty_inner_type("Vec", _type) {
                         quote!(                            
-                            PropType::arr(stringify!(#i_ty), self.#org_name.clone())
+                            PropType::arr(stringify!(#i_ty), self.#org_name.clone())?
                         )
                     } else {
                         // Страндартное предоразование значения в один из типов PropType
@@ -143,7 +175,7 @@ fn collect_props(cont: &ast::Container) -> Result<Vec<TokenStream>, Vec<syn::Err
                             PropType::from_type(
                                 stringify!(#_type), 
                                 Some(Box::new(self.#org_name.clone())),
-                            )
+                            )?
                         )
                     }
                 };
@@ -151,8 +183,7 @@ fn collect_props(cont: &ast::Container) -> Result<Vec<TokenStream>, Vec<syn::Err
             // Формирования финальной последовательность key, value для использования
             // в insert функции при сохранения параметров узла.
             quote! {
-                #set_name.to_string(),
-                #prop_value
+                mp.insert(#set_name.to_string(), #prop_value);
             }
         })
         .collect::<Vec<_>>();
@@ -160,6 +191,156 @@ fn collect_props(cont: &ast::Container) -> Result<Vec<TokenStream>, Vec<syn::Err
         Ok(output)
 }
 
+/// Запись поля со вложенной структурой, которая тоже реализует `NodeTrait`.
+/// Для остальных полей возвращается `None`.
+fn nested_props(field: &ast::Field) -> Option<TokenStream> {
+    let org_name = field.original.ident.as_ref().unwrap();
+    let set_name = field.attrs.name.settable.as_str();
+
+    // Свойства вложенной структуры, `None` если опциональное поле не заполнено
+    let nested = match ty_inner_type("Option", &field.original.ty) {
+        Some(_) => quote!(
+            self.#org_name.as_ref().map(|nested| nested.try_node("n")).transpose()?
+                .map(|nested| nested.props().clone().unwrap_or_default())
+        ),
+        None => quote!(
+            Some(self.#org_name.try_node("n")?.props().clone().unwrap_or_default())
+        ),
+    };
+
+    Some(match &field.attrs.nested {
+        crate::core::attr::Nested::None => return None,
+        // Свойства добавляются к свойствам узла с префиксом
+        crate::core::attr::Nested::Flatten(prefix) => quote!(
+            if let Some(nested) = #nested {
+                for (key, value) in nested {
+                    mp.insert(format!("{}{}", #prefix, key), value);
+                }
+            }
+        ),
+        crate::core::attr::Nested::Map => quote!(
+            mp.insert(#set_name.to_string(), match #nested {
+                Some(nested) => PropType::Map(nested),
+                None => PropType::Null,
+            });
+        ),
+        crate::core::attr::Nested::Json => quote!(
+            mp.insert(#set_name.to_string(), match #nested {
+                Some(nested) => PropType::str(PropType::Map(nested).to_json()),
+                None => PropType::Null,
+            });
+        ),
+    })
+}
+
+/// Преобразование поля временного типа (`chrono`, `time`, `std::time::Duration`)
+/// в PropType. Для остальных типов возвращается `None`.
+fn temporal_prop(field: &ast::Field) -> Option<TokenStream> {
+    let org_name = field.original.ident.as_ref().unwrap();
+    let option = ty_inner_type("Option", &field.original.ty);
+    let ty = option.unwrap_or(&field.original.ty);
+    let explicit = field.attrs.temporal;
+
+    // Тип, помеченный `#[cypher(temporal)]`, должен быть известным временным типом
+    let item_ty = ty_inner_type("Vec", ty).unwrap_or(ty);
+    if explicit && temporal_ctor(item_ty, true).is_none() {
+        return Some(
+            syn::Error::new_spanned(item_ty, "cypher `temporal` attribute requires a date, time or duration type")
+                .to_compile_error(),
+        );
+    }
+
+    // Массив значений временного типа
+    if let Some(i_ty) = ty_inner_type("Vec", ty) {
+        temporal_ctor(i_ty, explicit)?;
+
+        let array = quote!(PropType::Array(value.into_iter().map(PropType::from).collect()));
+        return Some(match option {
+            Some(_) => {
+                let defval = match &field.attrs.default {
+                    crate::core::attr::Default::Value(value) => quote!(Some(#value)),
+                    _ => quote!(None),
+                };
+
+                quote!(
+                    match self.#org_name.clone() {
+                        Some(value) => #array,
+                        None => PropType::str_arr(#defval),
+                    }
+                )
+            }
+            None => quote!({
+                let value = self.#org_name.clone();
+                #array
+            }),
+        });
+    }
+
+    let ctor = syn::Ident::new(temporal_ctor(ty, explicit)?, proc_macro2::Span::call_site());
+    Some(match option {
+        Some(_) => {
+            // Если значения нет, используется дефолтное значение или PropType::Null
+            let defval = match &field.attrs.default {
+                crate::core::attr::Default::None => quote!(PropType::Null),
+                crate::core::attr::Default::Default => quote!(
+                    PropType::from(<#ty as ::std::default::Default>::default())
+                ),
+                crate::core::attr::Default::Value(value) => quote!(PropType::#ctor(#value)),
+            };
+
+            quote!(
+                match self.#org_name.clone() {
+                    Some(value) => PropType::from(value),
+                    None => #defval,
+                }
+            )
+        }
+        None => quote!(PropType::from(self.#org_name.clone())),
+    })
+}
+
+/// Имя конструктора PropType, если тип является временным типом Neo4j.
+///
+/// Без атрибута `#[cypher(temporal)]` распознаются только типы, записанные
+/// полным путём: `chrono::NaiveDate`, `time::Date`, `std::time::Duration`,
+/// чтобы собственные типы пользователя с именами `Date` или `Duration`
+/// оставались строками.
+fn temporal_ctor(ty: &syn::Type, explicit: bool) -> Option<&'static str> {
+    let path = match ty {
+        syn::Type::Path(p) if p.qself.is_none() => &p.path,
+        _ => return None,
+    };
+    let segments = path.segments.iter().map(|s| s.ident.to_string()).collect::<Vec<_>>();
+    let (name, prefix) = segments.split_last()?;
+    let prefix = prefix.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+
+    let ctor = match (prefix.as_slice(), name.as_str()) {
+        (["chrono", ..], "NaiveDate") => "date",
+        (["chrono", ..], "NaiveTime") => "local_time",
+        (["chrono", ..], "NaiveDateTime") => "local_datetime",
+        (["chrono", ..], "DateTime") => "datetime",
+        (["chrono", ..], "TimeDelta" | "Duration") => "duration",
+
+        (["time", ..], "Date") => "date",
+        (["time", ..], "Time") => "local_time",
+        (["time", ..], "PrimitiveDateTime") => "local_datetime",
+        (["time", ..], "OffsetDateTime") => "datetime",
+        (["time", ..], "Duration") => "duration",
+
+        (["std" | "core", "time"], "Duration") => "duration",
+
+        // Импортированный тип, помеченный атрибутом `#[cypher(temporal)]`
+        (_, "NaiveDate" | "Date") if explicit => "date",
+        (_, "NaiveTime" | "Time") if explicit => "local_time",
+        (_, "NaiveDateTime" | "PrimitiveDateTime") if explicit => "local_datetime",
+        (_, "DateTime" | "OffsetDateTime") if explicit => "datetime",
+        (_, "TimeDelta" | "Duration") if explicit => "duration",
+        _ => return None,
+    };
+
+    Some(ctor)
+}
+
 /// Определение родителя дженерик типа
 fn ty_inner_type<'a>(wrapper: &str, ty: &'a syn::Type) -> Option<&'a syn::Type> {
     if let syn::Type::Path(ref p) = ty {
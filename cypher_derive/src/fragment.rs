@@ -1,6 +1,4 @@
 use proc_macro2::TokenStream;
-use quote::ToTokens;
-use syn::token;
 
 #[allow(dead_code)]
 pub enum Fragment {
@@ -11,28 +9,6 @@ pub enum Fragment {
     Block(TokenStream),
 }
 
-pub struct Expr(pub Fragment);
-impl ToTokens for Expr {
-    fn to_tokens(&self, out: &mut TokenStream) {
-        match &self.0 {
-            Fragment::Expr(expr) => expr.to_tokens(out),
-            Fragment::Block(block) => {
-                token::Brace::default().surround(out, |out| block.to_tokens(out));
-            }
-        }
-    }
-}
-
-pub struct Stmts(pub Fragment);
-impl ToTokens for Stmts {
-    fn to_tokens(&self, out: &mut TokenStream) {
-        match &self.0 {
-            Fragment::Expr(expr) => expr.to_tokens(out),
-            Fragment::Block(block) => block.to_tokens(out),
-        }
-    }
-}
-
 impl AsRef<TokenStream> for Fragment {
     fn as_ref(&self) -> &TokenStream {
         match self {
@@ -1,17 +1,37 @@
-use std::{collections::HashMap, fmt::Display};
+use std::{fmt::Display, iter::Peekable, rc::Rc, str::Chars};
+
+use crate::ident::{ident, Ident, IdentError};
 
 /// Internal types for use in entity properties.
+#[derive(Clone)]
 pub enum PropType {
-    Int(Box<dyn Display + 'static>),
-    String(Box<dyn Display + 'static>),
+    Int(Rc<dyn Display + 'static>),
+    /// Neo4j BOLT type Float.
+    ///
+    /// NaN and infinities have no literal in Cypher, so they are written
+    /// as `toFloat('NaN')`, `toFloat('Infinity')` and `toFloat('-Infinity')`.
+    Float(f64),
+    String(Rc<dyn Display + 'static>),
     Bool(bool),
     Array(Vec<Self>),
-    /// If a well-formed array already exists as a string.
-    /// For example: `['Bob', 'Tom']`.
-    ///
-    /// When forming a request, the result will be identical
-    /// to the `PropType::Array` type.
-    StrArr(String),
+    /// Neo4j BOLT type Map.
+    /// For example: `{name: 'Bob', age: 3}`.
+    Map(Props),
+    /// Neo4j temporal types, each holding its ISO 8601 text.
+    /// For example: `date('2024-05-01')`.
+    Date(String),
+    /// Time of day with an offset: `time('10:15:00+02:00')`.
+    Time(String),
+    /// Time of day without a time zone: `localtime('10:15:00')`.
+    LocalTime(String),
+    /// Date and time with an offset or a time zone: `datetime('2024-05-01T10:15:00Z')`.
+    DateTime(String),
+    /// Date and time without a time zone: `localdatetime('2024-05-01T10:15:00')`.
+    LocalDateTime(String),
+    /// Amount of time: `duration('P1DT2H')`.
+    Duration(String),
+    /// Neo4j spatial type Point: `point({x: 1.0, y: 2.0, crs: 'cartesian'})`.
+    Point(Point),
     /// Neo4j BOLT type NULL
     Null,
 }
@@ -22,7 +42,25 @@ impl PropType {
     where
         T: std::fmt::Display + 'static,
     {
-        PropType::Int(Box::new(value))
+        PropType::Int(Rc::new(value))
+    }
+
+    /// Create properties type `Int`, checking that the value fits into
+    /// a Neo4j Integer, which is a signed 64-bit number.
+    pub fn try_int<T>(value: T) -> Result<PropType, IntRangeError>
+    where
+        T: std::fmt::Display + 'static,
+    {
+        let text = value.to_string();
+        match text.parse::<i64>() {
+            Ok(_) => Ok(PropType::Int(Rc::new(value))),
+            Err(_) => Err(IntRangeError(text)),
+        }
+    }
+
+    /// Create properties type `Float`
+    pub fn float<T: Into<f64>>(value: T) -> PropType {
+        PropType::Float(value.into())
     }
 
     /// Create properties type `String`
@@ -30,27 +68,63 @@ impl PropType {
     where
         T: std::fmt::Display + 'static,
     {
-        PropType::String(Box::new(value))
+        PropType::String(Rc::new(value))
     }
 
-    /// Create properties type `Array`
-    pub fn arr<T>(ty: &str, value: Vec<T>) -> PropType
+    /// Create properties type `Array` from values of the Rust type named `ty`,
+    /// see [`PropType::from_type`].
+    pub fn arr<T>(ty: &str, value: Vec<T>) -> Result<PropType, IntRangeError>
     where
         T: std::fmt::Display + 'static,
     {
-        PropType::Array(
+        Ok(PropType::Array(
             value
                 .into_iter()
                 .map(|item| PropType::from_type(ty, Some(Box::new(item))))
-                .collect::<Vec<Self>>(),
-        )
+                .collect::<Result<Vec<Self>, _>>()?,
+        ))
+    }
+
+    /// Create properties type `Date` from an ISO 8601 date: `2024-05-01`.
+    pub fn date<T: Display>(value: T) -> PropType {
+        PropType::Date(value.to_string())
+    }
+
+    /// Create properties type `Time` from an ISO 8601 time with an offset: `10:15:00+02:00`.
+    pub fn time<T: Display>(value: T) -> PropType {
+        PropType::Time(value.to_string())
+    }
+
+    /// Create properties type `LocalTime` from an ISO 8601 time: `10:15:00`.
+    pub fn local_time<T: Display>(value: T) -> PropType {
+        PropType::LocalTime(value.to_string())
+    }
+
+    /// Create properties type `DateTime` from an ISO 8601 date and time
+    /// with an offset or a time zone: `2024-05-01T10:15:00+02:00`.
+    pub fn datetime<T: Display>(value: T) -> PropType {
+        PropType::DateTime(value.to_string())
+    }
+
+    /// Create properties type `LocalDateTime` from an ISO 8601 date and time: `2024-05-01T10:15:00`.
+    pub fn local_datetime<T: Display>(value: T) -> PropType {
+        PropType::LocalDateTime(value.to_string())
+    }
+
+    /// Create properties type `Duration` from an ISO 8601 duration: `P1DT2H`.
+    pub fn duration<T: Display>(value: T) -> PropType {
+        PropType::Duration(value.to_string())
     }
 
-    /// Create type PropType::Array if the array is already well-formed.
-    /// For example: `['Bob', 'Tom']`.
+    /// Create type PropType::Array from a well-formed list literal of strings,
+    /// numbers, booleans and nulls. For example: `['Bob', 'Tom']`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the text is not a list literal.
     pub fn str_arr(value: Option<&str>) -> PropType {
         if let Some(value) = value {
-            PropType::StrArr(value.to_string())
+            parse_list(value).unwrap_or_else(|| panic!("{:?} is not a list literal", value))
         } else {
             PropType::Null
         }
@@ -60,38 +134,226 @@ impl PropType {
 impl PartialEq for PropType {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
-            (Self::Int(_), Self::Int(_)) => true,
-            (Self::String(_), Self::String(_)) => true,
-            _ => core::mem::discriminant(self) == core::mem::discriminant(other),
+            (Self::Int(a), Self::Int(b)) => a.to_string() == b.to_string(),
+            (Self::Float(a), Self::Float(b)) => a == b,
+            (Self::String(a), Self::String(b)) => a.to_string() == b.to_string(),
+            (Self::Bool(a), Self::Bool(b)) => a == b,
+            (Self::Array(a), Self::Array(b)) => a == b,
+            (Self::Map(a), Self::Map(b)) => a == b,
+            (Self::Date(a), Self::Date(b)) => a == b,
+            (Self::Time(a), Self::Time(b)) => a == b,
+            (Self::LocalTime(a), Self::LocalTime(b)) => a == b,
+            (Self::DateTime(a), Self::DateTime(b)) => a == b,
+            (Self::LocalDateTime(a), Self::LocalDateTime(b)) => a == b,
+            (Self::Duration(a), Self::Duration(b)) => a == b,
+            (Self::Point(a), Self::Point(b)) => a == b,
+            (Self::Null, Self::Null) => true,
+            _ => false,
+        }
+    }
+}
+
+impl std::fmt::Debug for PropType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Int(value) => f.debug_tuple("Int").field(&value.to_string()).finish(),
+            Self::Float(value) => f.debug_tuple("Float").field(value).finish(),
+            Self::String(value) => f.debug_tuple("String").field(&value.to_string()).finish(),
+            Self::Bool(value) => f.debug_tuple("Bool").field(value).finish(),
+            Self::Array(value) => f.debug_tuple("Array").field(value).finish(),
+            Self::Map(value) => f.debug_tuple("Map").field(value).finish(),
+            Self::Date(value) => f.debug_tuple("Date").field(value).finish(),
+            Self::Time(value) => f.debug_tuple("Time").field(value).finish(),
+            Self::LocalTime(value) => f.debug_tuple("LocalTime").field(value).finish(),
+            Self::DateTime(value) => f.debug_tuple("DateTime").field(value).finish(),
+            Self::LocalDateTime(value) => f.debug_tuple("LocalDateTime").field(value).finish(),
+            Self::Duration(value) => f.debug_tuple("Duration").field(value).finish(),
+            Self::Point(value) => f.debug_tuple("Point").field(value).finish(),
+            Self::Null => write!(f, "Null"),
         }
     }
 }
 
+/// A point in one of the coordinate reference systems supported by Neo4j.
+///
+/// Geographic points are written with `x` as the longitude and `y` as the latitude.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Point {
+    Cartesian {
+        x: f64,
+        y: f64,
+    },
+    Cartesian3D {
+        x: f64,
+        y: f64,
+        z: f64,
+    },
+    Wgs84 {
+        longitude: f64,
+        latitude: f64,
+    },
+    Wgs84_3D {
+        longitude: f64,
+        latitude: f64,
+        height: f64,
+    },
+}
+
+impl Point {
+    pub fn cartesian(x: f64, y: f64) -> Self {
+        Point::Cartesian { x, y }
+    }
+
+    pub fn cartesian_3d(x: f64, y: f64, z: f64) -> Self {
+        Point::Cartesian3D { x, y, z }
+    }
+
+    pub fn wgs84(longitude: f64, latitude: f64) -> Self {
+        Point::Wgs84 {
+            longitude,
+            latitude,
+        }
+    }
+
+    pub fn wgs84_3d(longitude: f64, latitude: f64, height: f64) -> Self {
+        Point::Wgs84_3D {
+            longitude,
+            latitude,
+            height,
+        }
+    }
+
+    /// Name of the coordinate reference system.
+    pub fn crs(&self) -> &'static str {
+        match self {
+            Point::Cartesian { .. } => "cartesian",
+            Point::Cartesian3D { .. } => "cartesian-3d",
+            Point::Wgs84 { .. } => "wgs-84",
+            Point::Wgs84_3D { .. } => "wgs-84-3d",
+        }
+    }
+
+    /// Coordinates in the `x`, `y` and, for 3D points, `z` order.
+    pub fn coords(&self) -> Vec<f64> {
+        match *self {
+            Point::Cartesian { x, y } => vec![x, y],
+            Point::Cartesian3D { x, y, z } => vec![x, y, z],
+            Point::Wgs84 {
+                longitude,
+                latitude,
+            } => vec![longitude, latitude],
+            Point::Wgs84_3D {
+                longitude,
+                latitude,
+                height,
+            } => vec![longitude, latitude, height],
+        }
+    }
+
+    /// `point({x: 1.0, y: 2.0, crs: 'cartesian'})`
+    pub fn to_prop(&self) -> String {
+        let coords = ["x", "y", "z"]
+            .iter()
+            .zip(self.coords())
+            .map(|(axis, value)| format!("{}: {}, ", axis, float_literal(value)))
+            .collect::<String>();
+
+        format!("point({{{}crs: '{}'}})", coords, self.crs())
+    }
+}
+
+impl From<Point> for PropType {
+    fn from(value: Point) -> Self {
+        PropType::Point(value)
+    }
+}
+
+/// A value that does not fit into a Neo4j Integer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntRangeError(pub String);
+
+impl Display for IntRangeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} does not fit into a 64-bit Neo4j Integer", self.0)
+    }
+}
+
+impl std::error::Error for IntRangeError {}
+
+/// Reasons why a node can not be identified by its key properties.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyError {
+    /// A key is not a valid identifier.
+    Ident(IdentError),
+    /// A key property is missing or Null, so the pattern would match
+    /// every node with the same label.
+    Missing(String),
+    /// A property value does not fit into a Neo4j Integer.
+    IntRange(IntRangeError),
+}
+
+impl Display for KeyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeyError::Ident(err) => write!(f, "{}", err),
+            KeyError::Missing(key) => write!(f, "key property {:?} is missing or Null", key),
+            KeyError::IntRange(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for KeyError {}
+
+impl From<IdentError> for KeyError {
+    fn from(err: IdentError) -> Self {
+        KeyError::Ident(err)
+    }
+}
+
+impl From<IntRangeError> for KeyError {
+    fn from(err: IntRangeError) -> Self {
+        KeyError::IntRange(err)
+    }
+}
+
 impl PropType {
-    pub fn from_type(tt: &str, value: Option<Box<dyn Display + 'static>>) -> PropType {
+    /// Convert a value of the Rust type named `tt` into a property.
+    ///
+    /// Returns an error if a `u64`, `u128`, `i128` or `usize` value is out of
+    /// the range of a Neo4j Integer, see [`PropType::try_int`].
+    pub fn from_type(
+        tt: &str,
+        value: Option<Box<dyn Display + 'static>>,
+    ) -> Result<PropType, IntRangeError> {
         if let Some(value) = value {
-            return match tt {
+            let value: Rc<dyn Display> = Rc::from(value);
+
+            return Ok(match tt {
                 "String" => PropType::String(value),
-                "i128" | "i64" | "i32" | "i16" | "i8" => PropType::Int(value),
-                "u128" | "u64" | "u32" | "u16" | "u8" => PropType::Int(value),
-                "usize" | "isize" => PropType::Int(value),
-                "bool" => PropType::Bool(if value.to_string() == "true" {
-                    true
-                } else {
-                    false
-                }),
+                "i64" | "i32" | "i16" | "i8" | "isize" => PropType::Int(value),
+                "u32" | "u16" | "u8" => PropType::Int(value),
+                "i128" | "u128" | "u64" | "usize" => match value.to_string().parse::<i64>() {
+                    Ok(_) => PropType::Int(value),
+                    Err(_) => return Err(IntRangeError(value.to_string())),
+                },
+                "f64" | "f32" => match value.to_string().parse::<f64>() {
+                    Ok(value) => PropType::Float(value),
+                    Err(_) => PropType::String(value),
+                },
+                "bool" => PropType::Bool(value.to_string() == "true"),
 
                 _ => PropType::String(value),
-            };
+            });
         };
 
-        return PropType::Null;
+        Ok(PropType::Null)
     }
 
     pub fn to_prop(&self) -> String {
         match self {
             PropType::Int(value) => value.as_ref().to_string(),
-            PropType::String(value) => format!("'{}'", value.as_ref()),
+            PropType::Float(value) => float_literal(*value),
+            PropType::String(value) => str_literal(&value.to_string()),
             PropType::Bool(value) => value.to_string(),
             PropType::Array(value) => {
                 let mut body = value
@@ -102,19 +364,400 @@ impl PropType {
 
                 format!("[{}]", body)
             }
-            PropType::StrArr(value) => value.to_string(),
+            PropType::Map(value) => {
+                let mut body = value
+                    .iter()
+                    .map(|(k, v)| format!("{}: {},", ident(k), v.to_prop()))
+                    .collect::<String>();
+                body.pop();
+
+                format!("{{{}}}", body)
+            }
+            PropType::Date(value) => format!("date({})", str_literal(value)),
+            PropType::Time(value) => format!("time({})", str_literal(value)),
+            PropType::LocalTime(value) => format!("localtime({})", str_literal(value)),
+            PropType::DateTime(value) => format!("datetime({})", str_literal(value)),
+            PropType::LocalDateTime(value) => format!("localdatetime({})", str_literal(value)),
+            PropType::Duration(value) => format!("duration({})", str_literal(value)),
+            PropType::Point(value) => value.to_prop(),
             PropType::Null => String::from("NULL"),
         }
     }
+
+    /// Render the value as JSON, for storing a whole structure in a single
+    /// string property.
+    ///
+    /// Temporal values become their ISO 8601 text, NaN and infinities become `null`.
+    pub fn to_json(&self) -> String {
+        match self {
+            PropType::Int(value) => value.to_string(),
+            PropType::Float(value) if value.is_finite() => format!("{:?}", value),
+            PropType::Float(_) | PropType::Null => String::from("null"),
+            PropType::String(value) => json_str(&value.to_string()),
+            PropType::Bool(value) => value.to_string(),
+            PropType::Array(value) => {
+                let body = value.iter().map(PropType::to_json).collect::<Vec<_>>();
+                format!("[{}]", body.join(","))
+            }
+            PropType::Map(value) => {
+                let body = value
+                    .iter()
+                    .map(|(k, v)| format!("{}:{}", json_str(k), v.to_json()))
+                    .collect::<Vec<_>>();
+                format!("{{{}}}", body.join(","))
+            }
+            PropType::Date(value)
+            | PropType::Time(value)
+            | PropType::LocalTime(value)
+            | PropType::DateTime(value)
+            | PropType::LocalDateTime(value)
+            | PropType::Duration(value) => json_str(value),
+            PropType::Point(value) => {
+                let coords = ["x", "y", "z"]
+                    .iter()
+                    .zip(value.coords())
+                    .map(|(axis, v)| format!("\"{}\":{:?},", axis, v))
+                    .collect::<String>();
+                format!("{{{}\"crs\":\"{}\"}}", coords, value.crs())
+            }
+        }
+    }
+}
+
+/// Render a value as a JSON string.
+fn json_str(value: &str) -> String {
+    let mut json = String::with_capacity(value.len() + 2);
+    json.push('"');
+
+    for ch in value.chars() {
+        match ch {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            ch if ch.is_control() => json.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => json.push(ch),
+        }
+    }
+
+    json.push('"');
+    json
+}
+
+/// Parse a list literal of strings, numbers, booleans and nulls: `['Bob', 'Tom']`.
+fn parse_list(text: &str) -> Option<PropType> {
+    let mut chars = text.chars().peekable();
+    let list = parse_item(&mut chars)?;
+    skip_whitespace(&mut chars);
+
+    match list {
+        PropType::Array(_) if chars.peek().is_none() => Some(list),
+        _ => None,
+    }
+}
+
+fn parse_item(chars: &mut Peekable<Chars<'_>>) -> Option<PropType> {
+    skip_whitespace(chars);
+
+    match *chars.peek()? {
+        '[' => {
+            chars.next();
+            let mut items = Vec::new();
+            skip_whitespace(chars);
+            if chars.next_if_eq(&']').is_some() {
+                return Some(PropType::Array(items));
+            }
+
+            loop {
+                items.push(parse_item(chars)?);
+                skip_whitespace(chars);
+                match chars.next()? {
+                    ',' => continue,
+                    ']' => return Some(PropType::Array(items)),
+                    _ => return None,
+                }
+            }
+        }
+        quote @ ('\'' | '"') => {
+            chars.next();
+            let mut value = String::new();
+            loop {
+                match chars.next()? {
+                    '\\' => match chars.next()? {
+                        'n' => value.push('\n'),
+                        'r' => value.push('\r'),
+                        't' => value.push('\t'),
+                        ch => value.push(ch),
+                    },
+                    ch if ch == quote => return Some(PropType::str(value)),
+                    ch => value.push(ch),
+                }
+            }
+        }
+        _ => {
+            let mut word = String::new();
+            while let Some(ch) = chars.next_if(|ch| ch.is_alphanumeric() || "+-.".contains(*ch)) {
+                word.push(ch);
+            }
+
+            match word.to_lowercase().as_str() {
+                "true" => Some(PropType::Bool(true)),
+                "false" => Some(PropType::Bool(false)),
+                "null" => Some(PropType::Null),
+                _ => match word.parse::<i64>() {
+                    Ok(value) => Some(PropType::int(value)),
+                    Err(_) => word.parse::<f64>().ok().map(PropType::Float),
+                },
+            }
+        }
+    }
+}
+
+fn skip_whitespace(chars: &mut Peekable<Chars<'_>>) {
+    while chars.next_if(|ch| ch.is_whitespace()).is_some() {}
+}
+
+/// Render a number as a Cypher float literal.
+///
+/// Whole numbers keep their fractional part, so `1.0` is not read back as an Integer.
+fn float_literal(value: f64) -> String {
+    if value.is_nan() {
+        String::from("toFloat('NaN')")
+    } else if value.is_infinite() {
+        let sign = if value < 0.0 { "-" } else { "" };
+        format!("toFloat('{sign}Infinity')")
+    } else {
+        format!("{:?}", value)
+    }
+}
+
+/// Render a value as a single-quoted Cypher string literal.
+///
+/// Quotes, backslashes and characters that can not appear verbatim in a
+/// literal (control characters, line and paragraph separators) are escaped,
+/// so the result always parses back as exactly one string with the same content.
+pub fn str_literal(value: &str) -> String {
+    let mut literal = String::with_capacity(value.len() + 2);
+    literal.push('\'');
+
+    for ch in value.chars() {
+        match ch {
+            '\'' => literal.push_str("\\'"),
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            '\u{8}' => literal.push_str("\\b"),
+            '\u{c}' => literal.push_str("\\f"),
+            ch if ch.is_control() || ch == '\u{2028}' || ch == '\u{2029}' => {
+                literal.push_str(&format!("\\u{:04X}", ch as u32))
+            }
+            ch => literal.push(ch),
+        }
+    }
+
+    literal.push('\'');
+    literal
 }
 
 /// An object for parameters that can be used with any Neo4j entity.
-pub type Props = HashMap<String, PropType>;
+///
+/// Properties keep the order in which they were inserted, so derived structs
+/// render their fields in declaration order. Inserting a key that already
+/// exists replaces its value in place.
+#[derive(Clone, Debug, Default)]
+pub struct Props {
+    entries: Vec<(String, PropType)>,
+}
+
+type Entry = (String, PropType);
+
+impl Props {
+    pub fn new() -> Self {
+        Props::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Insert a property, returning the previous value of the key.
+    pub fn insert(&mut self, key: String, value: PropType) -> Option<PropType> {
+        match self.get_mut(&key) {
+            Some(prev) => Some(std::mem::replace(prev, value)),
+            None => {
+                self.entries.push((key, value));
+                None
+            }
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&PropType> {
+        self.entries.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut PropType> {
+        self.entries
+            .iter_mut()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v)
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Remove a property, keeping the order of the rest of them.
+    pub fn remove(&mut self, key: &str) -> Option<PropType> {
+        let index = self.entries.iter().position(|(k, _)| k == key)?;
+        Some(self.entries.remove(index).1)
+    }
+
+    pub fn retain<F: FnMut(&String, &mut PropType) -> bool>(&mut self, mut f: F) {
+        self.entries.retain_mut(|(k, v)| f(k, v));
+    }
+
+    pub fn iter(&self) -> PropsIter<'_> {
+        self.entries.iter().map(|(k, v)| (k, v))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &String> {
+        self.entries.iter().map(|(k, _)| k)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &PropType> {
+        self.entries.iter().map(|(_, v)| v)
+    }
+}
+
+/// Iterator over the properties in insertion order.
+pub type PropsIter<'a> =
+    std::iter::Map<std::slice::Iter<'a, Entry>, fn(&'a Entry) -> (&'a String, &'a PropType)>;
+
+/// Maps are equal when they have the same properties, whatever their order.
+impl PartialEq for Props {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|(k, v)| other.get(k) == Some(v))
+    }
+}
+
+impl std::ops::Index<&str> for Props {
+    type Output = PropType;
+
+    fn index(&self, key: &str) -> &PropType {
+        self.get(key)
+            .unwrap_or_else(|| panic!("no property `{}`", key))
+    }
+}
+
+impl FromIterator<Entry> for Props {
+    fn from_iter<I: IntoIterator<Item = Entry>>(iter: I) -> Self {
+        let mut props = Props::new();
+        props.extend(iter);
+        props
+    }
+}
+
+impl Extend<Entry> for Props {
+    fn extend<I: IntoIterator<Item = Entry>>(&mut self, iter: I) {
+        for (k, v) in iter {
+            self.insert(k, v);
+        }
+    }
+}
+
+impl<const N: usize> From<[Entry; N]> for Props {
+    fn from(entries: [Entry; N]) -> Self {
+        entries.into_iter().collect()
+    }
+}
+
+impl IntoIterator for Props {
+    type Item = Entry;
+    type IntoIter = std::vec::IntoIter<Entry>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Props {
+    type Item = (&'a String, &'a PropType);
+    type IntoIter = PropsIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 /// Inner wrapper for any type that can be cast to a string and stored as a node label
 pub type Label = Box<dyn Display>;
 
 pub trait NodeTrait: 'static + Sized {
-    fn node(&self, nv: &str) -> Node;
+    fn node(&self, nv: &str) -> Node<'_>;
+
+    /// A node whose property values are checked to fit into Neo4j types.
+    ///
+    /// The derive macro returns an error for a `u64`, `u128`, `i128` or `usize`
+    /// value out of the range of a Neo4j Integer, while `node` panics on it.
+    fn try_node(&self, nv: &str) -> Result<Node<'_>, IntRangeError> {
+        Ok(self.node(nv))
+    }
+
+    /// Names of the properties that identify the node.
+    ///
+    /// The derive macro fills it from fields marked with `#[cypher(key)]`.
+    fn keys(&self) -> Vec<&'static str> {
+        Vec::new()
+    }
+
+    /// A node for an idempotent **MERGE**.
+    ///
+    /// Only key properties are used in the **MERGE** pattern, the rest of them
+    /// are written into **ON CREATE SET** and **ON MATCH SET**.
+    /// If the node has no keys, all properties are used in the pattern.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a key property is missing or Null, see [`NodeTrait::try_merge_node`].
+    fn merge_node(&self, nv: &str) -> Node<'_> {
+        self.try_merge_node(nv)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// A node for an idempotent **MERGE**, checking that every key property has a value.
+    fn try_merge_node(&self, nv: &str) -> Result<Node<'_>, KeyError> {
+        self.try_node(nv)?.try_with_keys(self.keys())
+    }
+
+    /// A node for a **MATCH** by its key properties:
+    ///
+    /// `MATCH (n:Label { key: ... })`
+    ///
+    /// # Panics
+    ///
+    /// Panics if a key property is missing or Null, see [`NodeTrait::try_match_node`].
+    fn match_node(&self, nv: &str) -> Node<'_> {
+        self.try_match_node(nv)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// A node for a **MATCH** by its key properties, checking that every key property has a value.
+    fn try_match_node(&self, nv: &str) -> Result<Node<'_>, KeyError> {
+        let keys = self.keys();
+        let mut node = self.try_node(nv)?;
+        if let Some(props) = node.props.as_mut() {
+            props.retain(|k, _| keys.contains(&k.as_str()));
+        }
+
+        node.try_with_keys(keys)
+    }
 }
 
 pub struct Node<'a> {
@@ -122,21 +765,73 @@ pub struct Node<'a> {
     node_name: &'a str,
     props: Option<Props>,
     labels: Option<Vec<Label>>,
+    keys: Option<Vec<&'a str>>,
 }
 
 impl<'a> Node<'a> {
+    /// # Panics
+    ///
+    /// Panics if one of the names is not a valid identifier, see [`Node::try_new`].
     pub fn new<T: Display>(
         nv: T,
         node_name: &'a str,
         props: Option<Props>,
         labels: Option<Vec<Label>>,
     ) -> Self {
-        Node {
-            nv: nv.to_string(),
+        Self::try_new(nv, node_name, props, labels).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Create a node, checking that its variable, name, labels and
+    /// property keys are valid identifiers.
+    pub fn try_new<T: Display>(
+        nv: T,
+        node_name: &'a str,
+        props: Option<Props>,
+        labels: Option<Vec<Label>>,
+    ) -> Result<Self, IdentError> {
+        let nv = Ident::new(nv)?.as_str().to_string();
+        Ident::new(node_name)?;
+        for label in labels.iter().flatten() {
+            Ident::new(label)?;
+        }
+        check_keys(&props)?;
+
+        Ok(Node {
+            nv,
             node_name,
             props,
             labels,
+            keys: None,
+        })
+    }
+
+    /// Mark properties that identify the node.
+    ///
+    /// Keyed nodes are matched and merged by these properties only.
+    ///
+    /// # Panics
+    ///
+    /// Panics if one of the keys is not a valid identifier or the node has
+    /// no value for it, see [`Node::try_with_keys`].
+    pub fn with_keys(self, keys: Vec<&'a str>) -> Self {
+        self.try_with_keys(keys)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Mark properties that identify the node, checking that the keys are valid
+    /// identifiers and that none of the key properties is missing or Null.
+    pub fn try_with_keys(mut self, keys: Vec<&'a str>) -> Result<Self, KeyError> {
+        for key in &keys {
+            Ident::new(key)?;
+
+            let value = self.props.as_ref().and_then(|props| props.get(key));
+            if matches!(value, None | Some(PropType::Null)) {
+                return Err(KeyError::Missing(key.to_string()));
+            }
         }
+
+        self.keys = if keys.is_empty() { None } else { Some(keys) };
+        Ok(self)
     }
 
     pub fn nv(&self) -> &str {
@@ -144,7 +839,7 @@ impl<'a> Node<'a> {
     }
 
     pub fn node_name(&self) -> &str {
-        &self.node_name
+        self.node_name
     }
 
     pub fn props(&self) -> &Option<Props> {
@@ -154,6 +849,10 @@ impl<'a> Node<'a> {
     pub fn labels(&self) -> &Option<Vec<Label>> {
         &self.labels
     }
+
+    pub fn keys(&self) -> &Option<Vec<&'a str>> {
+        &self.keys
+    }
 }
 
 pub struct Relation<'a> {
@@ -164,20 +863,36 @@ pub struct Relation<'a> {
 }
 
 impl<'a> Relation<'a> {
+    /// # Panics
+    ///
+    /// Panics if the type or a property key is not a valid identifier, see [`Relation::try_new`].
     pub fn new(from: Node<'a>, to: Node<'a>, name: &'a str, props: Option<Props>) -> Self {
-        Relation {
+        Self::try_new(from, to, name, props).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Create a relationship, checking that its type and property keys are valid identifiers.
+    pub fn try_new(
+        from: Node<'a>,
+        to: Node<'a>,
+        name: &'a str,
+        props: Option<Props>,
+    ) -> Result<Self, IdentError> {
+        Ident::new(name)?;
+        check_keys(&props)?;
+
+        Ok(Relation {
             from,
             to,
             name,
             props,
-        }
+        })
     }
 
-    pub fn from_node(&self) -> &Node {
+    pub fn from_node(&self) -> &Node<'_> {
         &self.from
     }
 
-    pub fn to_node(&self) -> &Node {
+    pub fn to_node(&self) -> &Node<'_> {
         &self.to
     }
 
@@ -190,6 +905,15 @@ impl<'a> Relation<'a> {
     }
 }
 
+/// Check that all property keys are valid identifiers.
+fn check_keys(props: &Option<Props>) -> Result<(), IdentError> {
+    for key in props.iter().flat_map(Props::keys) {
+        Ident::new(key)?;
+    }
+
+    Ok(())
+}
+
 /// Entities existing in Neo4j. Nodes and relationships.
 pub enum Entity<'a> {
     Node {
@@ -197,6 +921,7 @@ pub enum Entity<'a> {
         node_name: &'a str,
         props: Option<Props>,
         labels: Option<Vec<Label>>,
+        keys: Option<Vec<&'a str>>,
     },
 
     Relation {
@@ -214,6 +939,7 @@ impl<'a> From<Node<'a>> for Entity<'a> {
             node_name: node.node_name,
             props: node.props,
             labels: node.labels,
+            keys: node.keys,
         }
     }
 }
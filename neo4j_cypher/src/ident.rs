@@ -0,0 +1,132 @@
+use std::fmt::{self, Display};
+
+/// Words that can not be used as bare identifiers.
+const RESERVED: &[&str] = &[
+    "ALL",
+    "AND",
+    "AS",
+    "ASC",
+    "ASCENDING",
+    "BY",
+    "CALL",
+    "CASE",
+    "CONTAINS",
+    "CREATE",
+    "DELETE",
+    "DESC",
+    "DESCENDING",
+    "DETACH",
+    "DISTINCT",
+    "ELSE",
+    "END",
+    "ENDS",
+    "EXISTS",
+    "FALSE",
+    "FOREACH",
+    "IN",
+    "IS",
+    "LIMIT",
+    "LOAD",
+    "MATCH",
+    "MERGE",
+    "NOT",
+    "NULL",
+    "ON",
+    "OPTIONAL",
+    "OR",
+    "ORDER",
+    "REMOVE",
+    "RETURN",
+    "SET",
+    "SKIP",
+    "STARTS",
+    "THEN",
+    "TRUE",
+    "UNION",
+    "UNWIND",
+    "USE",
+    "WHEN",
+    "WHERE",
+    "WITH",
+    "XOR",
+    "YIELD",
+];
+
+/// Reasons why a name can not be used as an identifier.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IdentError {
+    /// The name is an empty string.
+    Empty,
+    /// The name contains the `\u0000` character, which Neo4j rejects even when escaped.
+    NullChar(String),
+}
+
+impl Display for IdentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IdentError::Empty => write!(f, "identifier can not be empty"),
+            IdentError::NullChar(name) => {
+                write!(f, "identifier {:?} can not contain a null character", name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for IdentError {}
+
+/// A validated name of a variable, label, relationship type or property key.
+///
+/// When written into a query the name is left as is if it is a plain
+/// identifier, otherwise it is wrapped in backticks with embedded backticks
+/// doubled. For example: `Order` becomes `` `Order` `` and `my label` becomes `` `my label` ``.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ident(String);
+
+impl Ident {
+    pub fn new<T: Display>(name: T) -> Result<Self, IdentError> {
+        let name = name.to_string();
+
+        if name.is_empty() {
+            return Err(IdentError::Empty);
+        }
+        if name.contains('\0') {
+            return Err(IdentError::NullChar(name));
+        }
+
+        Ok(Ident(name))
+    }
+
+    /// The name without any escaping.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Whether the name must be wrapped in backticks to be used in a query.
+    pub fn needs_quoting(&self) -> bool {
+        let mut chars = self.0.chars();
+        let starts_well = matches!(chars.next(), Some(ch) if ch.is_ascii_alphabetic() || ch == '_');
+
+        !starts_well
+            || !chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+            || RESERVED.contains(&self.0.to_ascii_uppercase().as_str())
+    }
+}
+
+impl Display for Ident {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.needs_quoting() {
+            write!(f, "`{}`", self.0.replace('`', "``"))
+        } else {
+            f.write_str(&self.0)
+        }
+    }
+}
+
+/// Shorthand used by the query builders to write a name into a query.
+///
+/// # Panics
+///
+/// Panics if the name is not a valid identifier, see [`Ident::new`].
+pub(crate) fn ident<T: Display>(name: T) -> Ident {
+    Ident::new(name).unwrap_or_else(|err| panic!("{}", err))
+}
@@ -1,5 +1,7 @@
 pub mod query;
 pub mod entity;
+pub mod ident;
+mod temporal;
 
 #[cfg(feature = "derive")]
 pub use cypher_derive::CypQue;
\ No newline at end of file
@@ -0,0 +1,701 @@
+use crate::entity::{Node, PropType};
+use crate::query::match_query::{CompOper, OperandError};
+use crate::query::return_query::Order;
+
+/// A single clause of a query.
+///
+/// The query builders produce a list of clauses, which is turned into
+/// the query text only when the query is finalized.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Clause {
+    /// Query text that is written as is.
+    Raw(String),
+    /// `MATCH pattern WHERE predicate`
+    Match {
+        optional: bool,
+        pattern: Pattern,
+        predicate: Option<Expr>,
+    },
+    /// `CREATE pattern, pattern`
+    Create(Vec<Pattern>),
+    /// `MERGE pattern ON CREATE SET ... ON MATCH SET ...`
+    Merge {
+        pattern: Pattern,
+        actions: Vec<MergeAction>,
+    },
+    /// `SET item, item`
+    Set(Vec<SetItem>),
+    /// `DELETE var` or `DETACH DELETE var`
+    Delete { detach: bool, vars: Vec<String> },
+    /// `UNWIND list AS var`
+    Unwind { list: Expr, var: String },
+    /// `FOREACH (var IN list | body)`
+    Foreach {
+        var: String,
+        list: Expr,
+        body: Vec<Clause>,
+    },
+    /// `WITH item, item ORDER BY ... SKIP ... LIMIT ... WHERE predicate`
+    With(Projection),
+    /// `RETURN item, item` or `RETURN DISTINCT item, item`
+    Return {
+        distinct: bool,
+        items: Vec<ReturnItem>,
+    },
+    /// `ORDER BY expr ASC, expr DESC`
+    OrderBy(Vec<(Expr, Order)>),
+    /// `SKIP value`
+    Skip(usize),
+    /// `LIMIT value`
+    Limit(usize),
+}
+
+/// A pattern of nodes and relationships.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pattern {
+    /// `(n:Label { props })`
+    Node(NodePattern),
+    /// `(a)-[r:TYPE { props }]->(b)<-[:OTHER]-(c)`
+    Path(PathPattern),
+}
+
+impl From<NodePattern> for Pattern {
+    fn from(node: NodePattern) -> Self {
+        Pattern::Node(node)
+    }
+}
+
+impl From<PathPattern> for Pattern {
+    fn from(path: PathPattern) -> Self {
+        Pattern::Path(path)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct NodePattern {
+    pub var: Option<String>,
+    pub labels: Vec<String>,
+    /// Label expression, combined with `labels` by `&` if both are present.
+    pub label_expr: Option<LabelExpr>,
+    pub props: Vec<(String, Expr)>,
+}
+
+impl NodePattern {
+    /// A pattern that only refers to an already bound variable: `(n)`
+    pub fn var(var: &str) -> Self {
+        NodePattern {
+            var: Some(var.to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// Add a label: `(n:Label)`
+    pub fn label(mut self, label: &str) -> Self {
+        self.labels.push(label.to_string());
+        self
+    }
+
+    /// Add a property to the inline map: `(n { key: value })`
+    pub fn prop(mut self, key: &str, value: PropType) -> Self {
+        self.props.push((key.to_string(), Expr::Value(value)));
+        self
+    }
+
+    /// Match labels with an expression: `(n:A|B)`, `(n:!Deleted)`, `(n:%)`
+    pub fn label_expr(mut self, expr: LabelExpr) -> Self {
+        self.label_expr = Some(expr);
+        self
+    }
+}
+
+/// Label expression of a node pattern or a label predicate.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LabelExpr {
+    /// `Label`
+    Label(String),
+    /// `%`, any label.
+    Any,
+    /// `!expr`
+    Not(Box<LabelExpr>),
+    /// `left&right`
+    And(Box<LabelExpr>, Box<LabelExpr>),
+    /// `left|right`
+    Or(Box<LabelExpr>, Box<LabelExpr>),
+}
+
+impl LabelExpr {
+    /// `Label`
+    pub fn label(label: &str) -> Self {
+        LabelExpr::Label(label.to_string())
+    }
+
+    /// `%`, matches nodes with at least one label.
+    pub fn any() -> Self {
+        LabelExpr::Any
+    }
+
+    /// `self&other`
+    pub fn and(self, other: LabelExpr) -> Self {
+        LabelExpr::And(Box::new(self), Box::new(other))
+    }
+
+    /// `self|other`
+    pub fn or(self, other: LabelExpr) -> Self {
+        LabelExpr::Or(Box::new(self), Box::new(other))
+    }
+}
+
+/// `!expr`
+impl std::ops::Not for LabelExpr {
+    type Output = LabelExpr;
+
+    fn not(self) -> Self::Output {
+        LabelExpr::Not(Box::new(self))
+    }
+}
+
+/// Pattern of a node with all of its labels and non-null properties.
+impl From<&Node<'_>> for NodePattern {
+    fn from(node: &Node<'_>) -> Self {
+        let mut labels = vec![node.node_name().to_string()];
+        labels.extend(
+            node.labels()
+                .iter()
+                .flatten()
+                .map(|label| label.to_string()),
+        );
+
+        NodePattern {
+            var: Some(node.nv().to_string()),
+            labels,
+            label_expr: None,
+            props: node
+                .props()
+                .iter()
+                .flatten()
+                .filter(|(_, v)| **v != PropType::Null)
+                .map(|(k, v)| (k.clone(), Expr::Value(v.clone())))
+                .collect(),
+        }
+    }
+}
+
+/// Direction of a relationship in a pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// `-[]->`
+    Outgoing,
+    /// `<-[]-`
+    Incoming,
+    /// `-[]-`
+    Undirected,
+}
+
+/// Number of hops of a variable-length relationship: `*`, `*2`, `*1..3`, `*..3`, `*2..`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Length {
+    pub min: Option<usize>,
+    pub max: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelPattern {
+    pub var: Option<String>,
+    /// Alternative types, written as `:A|B`.
+    pub types: Vec<String>,
+    pub direction: Direction,
+    pub length: Option<Length>,
+    pub props: Vec<(String, Expr)>,
+}
+
+impl RelPattern {
+    /// `-[]->`
+    pub fn outgoing() -> Self {
+        Self::new(Direction::Outgoing)
+    }
+
+    /// `<-[]-`
+    pub fn incoming() -> Self {
+        Self::new(Direction::Incoming)
+    }
+
+    /// `-[]-`
+    pub fn undirected() -> Self {
+        Self::new(Direction::Undirected)
+    }
+
+    fn new(direction: Direction) -> Self {
+        RelPattern {
+            var: None,
+            types: Vec::new(),
+            direction,
+            length: None,
+            props: Vec::new(),
+        }
+    }
+
+    /// Name the relationship: `-[r]->`
+    pub fn var(mut self, var: &str) -> Self {
+        self.var = Some(var.to_string());
+        self
+    }
+
+    /// Add a type, several of them are alternatives: `-[:A|B]->`
+    pub fn r#type(mut self, rel_type: &str) -> Self {
+        self.types.push(rel_type.to_string());
+        self
+    }
+
+    /// Add a property to the inline map: `-[{ key: value }]->`
+    pub fn prop(mut self, key: &str, value: PropType) -> Self {
+        self.props.push((key.to_string(), Expr::Value(value)));
+        self
+    }
+
+    /// Variable length with optional bounds: `-[*1..3]->`
+    pub fn length(mut self, min: Option<usize>, max: Option<usize>) -> Self {
+        self.length = Some(Length { min, max });
+        self
+    }
+
+    /// Variable length without bounds: `-[*]->`
+    pub fn any_length(self) -> Self {
+        self.length(None, None)
+    }
+}
+
+/// Shortest path search wrapped around a path pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shortest {
+    /// `shortestPath(...)`
+    One,
+    /// `allShortestPaths(...)`
+    All,
+}
+
+/// A chain of nodes connected by relationships.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathPattern {
+    /// Path variable: `p = (a)-[]->(b)`
+    pub name: Option<String>,
+    pub shortest: Option<Shortest>,
+    pub start: NodePattern,
+    pub hops: Vec<Hop>,
+}
+
+/// A step of a path pattern.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Hop {
+    /// `-[r:TYPE]->(b)`
+    Rel(RelPattern, NodePattern),
+    /// ` ((x)-[:TYPE]->(y) WHERE predicate){1,5} (b)`
+    Quantified(QuantifiedPath, NodePattern),
+}
+
+/// How many times a quantified path repeats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quantifier {
+    /// `+`, one or more times.
+    Plus,
+    /// `*`, any number of times.
+    Star,
+    /// `{m,n}`, `{m,}`, `{,n}` or `{n}` if both bounds are equal,
+    /// a range without bounds is written as `*`.
+    Range {
+        min: Option<usize>,
+        max: Option<usize>,
+    },
+}
+
+/// A parenthesized path pattern that is repeated: `((a)-[:R]->(b) WHERE a.p > 1){1,5}`
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuantifiedPath {
+    pub path: PathPattern,
+    pub predicate: Option<Expr>,
+    pub quantifier: Quantifier,
+}
+
+impl QuantifiedPath {
+    /// Repeat `path`, it is expected to have at least one relationship.
+    pub fn new(path: PathPattern, quantifier: Quantifier) -> Self {
+        QuantifiedPath {
+            path,
+            predicate: None,
+            quantifier,
+        }
+    }
+
+    /// Condition checked on every repetition, written inside the group.
+    /// Repeated calls are joined with **AND**.
+    pub fn r#where(mut self, expr: Expr) -> Self {
+        self.predicate = Some(match self.predicate.take() {
+            Some(prev) => prev.and(expr),
+            None => expr,
+        });
+        self
+    }
+}
+
+impl PathPattern {
+    /// A path starting at `start`, hops are added with `hop`.
+    pub fn new(start: NodePattern) -> Self {
+        PathPattern {
+            name: None,
+            shortest: None,
+            start,
+            hops: Vec::new(),
+        }
+    }
+
+    /// Assign the path to a variable: `p = (a)-[]->(b)`
+    pub fn named(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Match only a single shortest path: `shortestPath((a)-[*]->(b))`
+    pub fn shortest(mut self) -> Self {
+        self.shortest = Some(Shortest::One);
+        self
+    }
+
+    /// Match all shortest paths: `allShortestPaths((a)-[*]->(b))`
+    pub fn all_shortest(mut self) -> Self {
+        self.shortest = Some(Shortest::All);
+        self
+    }
+
+    /// Continue the path with a relationship and the node it leads to.
+    pub fn hop(mut self, rel: RelPattern, node: NodePattern) -> Self {
+        self.hops.push(Hop::Rel(rel, node));
+        self
+    }
+
+    /// Continue the path with a quantified path and the node that follows it:
+    ///
+    /// `(a) ((x)-[:R]->(y)){1,5} (b)`
+    pub fn repeat(mut self, group: QuantifiedPath, node: NodePattern) -> Self {
+        self.hops.push(Hop::Quantified(group, node));
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MergeAction {
+    /// `ON CREATE SET item, item`
+    OnCreate(Vec<SetItem>),
+    /// `ON MATCH SET item, item`
+    OnMatch(Vec<SetItem>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SetItem {
+    /// `var.key = value`
+    Property {
+        var: String,
+        key: String,
+        value: Expr,
+    },
+    /// `var:Label:Other`
+    Labels { var: String, labels: Vec<String> },
+    /// `var = value`
+    Replace { var: String, value: Expr },
+    /// `var += value`
+    Extend { var: String, value: Expr },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReturnItem {
+    pub expr: Expr,
+    pub alias: Option<String>,
+}
+
+impl From<Expr> for ReturnItem {
+    fn from(expr: Expr) -> Self {
+        ReturnItem { expr, alias: None }
+    }
+}
+
+/// Body of a **WITH** clause.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Projection {
+    pub distinct: bool,
+    pub items: Vec<ReturnItem>,
+    pub order: Vec<(Expr, Order)>,
+    pub skip: Option<usize>,
+    pub limit: Option<usize>,
+    pub predicate: Option<Expr>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    /// A value supplied by the user.
+    ///
+    /// It is replaced by a placeholder when the query is built in parameter mode.
+    Value(PropType),
+    /// A value that is always written into the query text.
+    Literal(PropType),
+    /// A value that is always passed as the named parameter `$name`.
+    Param(String, PropType),
+    /// `var`
+    Var(String),
+    /// `expr.key`
+    Property(Box<Expr>, String),
+    /// `left op right`, or just `left op` for `IS NULL` and `IS NOT NULL`.
+    Compare(Box<Expr>, CompOper, Box<Expr>),
+    /// `NOT expr`
+    Not(Box<Expr>),
+    /// `left AND right`
+    And(Box<Expr>, Box<Expr>),
+    /// `left XOR right`
+    Xor(Box<Expr>, Box<Expr>),
+    /// `left OR right`
+    Or(Box<Expr>, Box<Expr>),
+    /// `(expr)`, parenthesized even where precedence does not require it.
+    Group(Box<Expr>),
+    /// `CASE WHEN when THEN then ELSE otherwise END`
+    Case {
+        when: Box<Expr>,
+        then: Box<Expr>,
+        otherwise: Box<Expr>,
+    },
+    /// `*`, all variables in scope.
+    All,
+    /// `expr:Label`, a label predicate.
+    HasLabels(Box<Expr>, LabelExpr),
+    /// `name(arg, ...)`
+    Function { name: String, args: Vec<Expr> },
+    /// `count(*)`
+    CountAll,
+    /// `func(DISTINCT arg, ...)`
+    Aggregate {
+        func: Aggregate,
+        distinct: bool,
+        args: Vec<Expr>,
+    },
+}
+
+/// Aggregating functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregate {
+    Count,
+    Sum,
+    Avg,
+    Min,
+    Max,
+    Collect,
+    PercentileCont,
+    PercentileDisc,
+    StDev,
+}
+
+impl std::fmt::Display for Aggregate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Aggregate::Count => write!(f, "count"),
+            Aggregate::Sum => write!(f, "sum"),
+            Aggregate::Avg => write!(f, "avg"),
+            Aggregate::Min => write!(f, "min"),
+            Aggregate::Max => write!(f, "max"),
+            Aggregate::Collect => write!(f, "collect"),
+            Aggregate::PercentileCont => write!(f, "percentileCont"),
+            Aggregate::PercentileDisc => write!(f, "percentileDisc"),
+            Aggregate::StDev => write!(f, "stDev"),
+        }
+    }
+}
+
+impl Expr {
+    /// `var`
+    pub fn var(var: &str) -> Self {
+        Expr::Var(var.to_string())
+    }
+
+    /// `var.key`
+    pub fn prop(var: &str, key: &str) -> Self {
+        Expr::Property(Box::new(Expr::var(var)), key.to_string())
+    }
+
+    /// Comparison of a property with a value: `var.key op value`
+    pub fn cond(var: &str, key: &str, op: CompOper, value: PropType) -> Self {
+        Expr::prop(var, key).compare(op, value)
+    }
+
+    /// `self op value`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value does not fit the operator, see `CompOper::check`.
+    pub fn compare(self, op: CompOper, value: PropType) -> Self {
+        self.try_compare(op, value)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// `self op value`, checking that the value fits the operator.
+    pub fn try_compare(self, op: CompOper, value: PropType) -> Result<Self, OperandError> {
+        op.check(&value)?;
+
+        Ok(Expr::Compare(
+            Box::new(self),
+            op,
+            Box::new(Expr::Value(value)),
+        ))
+    }
+
+    /// `self AND other`
+    pub fn and(self, other: Expr) -> Self {
+        Expr::And(Box::new(self), Box::new(other))
+    }
+
+    /// `self OR other`
+    pub fn or(self, other: Expr) -> Self {
+        Expr::Or(Box::new(self), Box::new(other))
+    }
+
+    /// `self XOR other`
+    pub fn xor(self, other: Expr) -> Self {
+        Expr::Xor(Box::new(self), Box::new(other))
+    }
+
+    /// `self AS alias`
+    pub fn r#as(self, alias: &str) -> ReturnItem {
+        ReturnItem {
+            expr: self,
+            alias: Some(alias.to_string()),
+        }
+    }
+
+    /// `*`, a projection item of all variables in scope.
+    pub fn all() -> Self {
+        Expr::All
+    }
+
+    /// Label predicate: `var:Admin`, `var:A|B`
+    pub fn has_labels(var: &str, labels: LabelExpr) -> Self {
+        Expr::HasLabels(Box::new(Expr::var(var)), labels)
+    }
+
+    /// `nodes(path)`
+    pub fn nodes(path: Expr) -> Self {
+        Expr::function("nodes", vec![path])
+    }
+
+    /// `relationships(path)`
+    pub fn relationships(path: Expr) -> Self {
+        Expr::function("relationships", vec![path])
+    }
+
+    /// `length(path)`
+    pub fn length(path: Expr) -> Self {
+        Expr::function("length", vec![path])
+    }
+
+    /// Distance between two points: `point.distance(from, to)`
+    pub fn distance(from: Expr, to: Expr) -> Self {
+        Expr::function("point.distance", vec![from, to])
+    }
+
+    /// Whether a point lies in a bounding box: `point.withinBBox(point, lowerLeft, upperRight)`
+    pub fn within_bbox(point: Expr, lower_left: Expr, upper_right: Expr) -> Self {
+        Expr::function("point.withinBBox", vec![point, lower_left, upper_right])
+    }
+
+    fn function(name: &str, args: Vec<Expr>) -> Self {
+        Expr::Function {
+            name: name.to_string(),
+            args,
+        }
+    }
+
+    /// `count(*)`
+    pub fn count_all() -> Self {
+        Expr::CountAll
+    }
+
+    /// `count(expr)`
+    pub fn count(expr: Expr) -> Self {
+        Expr::aggregate(Aggregate::Count, vec![expr])
+    }
+
+    /// `sum(expr)`
+    pub fn sum(expr: Expr) -> Self {
+        Expr::aggregate(Aggregate::Sum, vec![expr])
+    }
+
+    /// `avg(expr)`
+    pub fn avg(expr: Expr) -> Self {
+        Expr::aggregate(Aggregate::Avg, vec![expr])
+    }
+
+    /// `min(expr)`
+    pub fn min(expr: Expr) -> Self {
+        Expr::aggregate(Aggregate::Min, vec![expr])
+    }
+
+    /// `max(expr)`
+    pub fn max(expr: Expr) -> Self {
+        Expr::aggregate(Aggregate::Max, vec![expr])
+    }
+
+    /// `collect(expr)`
+    pub fn collect(expr: Expr) -> Self {
+        Expr::aggregate(Aggregate::Collect, vec![expr])
+    }
+
+    /// `percentileCont(expr, percentile)`, the percentile is between `0.0` and `1.0`.
+    pub fn percentile_cont(expr: Expr, percentile: f64) -> Self {
+        Expr::aggregate(
+            Aggregate::PercentileCont,
+            vec![expr, Expr::Literal(PropType::float(percentile))],
+        )
+    }
+
+    /// `percentileDisc(expr, percentile)`, the percentile is between `0.0` and `1.0`.
+    pub fn percentile_disc(expr: Expr, percentile: f64) -> Self {
+        Expr::aggregate(
+            Aggregate::PercentileDisc,
+            vec![expr, Expr::Literal(PropType::float(percentile))],
+        )
+    }
+
+    /// `stDev(expr)`
+    pub fn st_dev(expr: Expr) -> Self {
+        Expr::aggregate(Aggregate::StDev, vec![expr])
+    }
+
+    fn aggregate(func: Aggregate, args: Vec<Expr>) -> Self {
+        Expr::Aggregate {
+            func,
+            distinct: false,
+            args,
+        }
+    }
+
+    /// Aggregate only distinct values: `count(DISTINCT expr)`
+    ///
+    /// Has no effect on other expressions.
+    pub fn distinct(mut self) -> Self {
+        if let Expr::Aggregate { distinct, .. } = &mut self {
+            *distinct = true;
+        }
+
+        self
+    }
+
+    /// `(self)`
+    ///
+    /// Operands are parenthesized on rendering whenever precedence requires it,
+    /// so grouping is only needed to make the intent explicit in the query text.
+    pub fn group(self) -> Self {
+        Expr::Group(Box::new(self))
+    }
+}
+
+/// `NOT expr`, also available as `!expr`.
+impl std::ops::Not for Expr {
+    type Output = Expr;
+
+    fn not(self) -> Self::Output {
+        Expr::Not(Box::new(self))
+    }
+}
@@ -1,12 +1,34 @@
-pub struct Finalize(pub String);
+use crate::query::state::{Params, State};
+
+pub struct Finalize(pub State);
 
 pub trait FinalizeTrait: 'static {
     /// End the query building process and return a string.
     fn finalize(&self) -> String;
+
+    /// End the query building process and return a string together with
+    /// the parameters referenced by it.
+    ///
+    /// The map is only filled when the query was started with
+    /// `Query::init_with_params`, otherwise all values are already inlined
+    /// except the `$rows` of `create_unwind` and `merge_unwind`.
+    fn finalize_with_params(&self) -> (String, Params);
+
+    /// End the query building process and return the clauses of the query
+    /// without rendering them.
+    fn finalize_state(&self) -> State;
 }
 
 impl FinalizeTrait for Finalize {
     fn finalize(&self) -> String {
+        self.0.query()
+    }
+
+    fn finalize_with_params(&self) -> (String, Params) {
+        self.0.render()
+    }
+
+    fn finalize_state(&self) -> State {
         self.0.clone()
     }
 }
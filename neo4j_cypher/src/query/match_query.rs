@@ -1,9 +1,16 @@
-use crate::entity::{Entity, PropType};
+#[cfg(feature = "templates")]
+use crate::entity::Point;
+use crate::entity::{Entity, PropType, Props};
+use crate::query::ast::{Clause, Expr, Pattern, ReturnItem, SetItem};
 use crate::query::finalize::FinalizeTrait;
+use crate::query::merge_query::MergeTrait;
 use crate::query::return_query::{ReturnParamTrait, ReturnQuery, ReturnTrait};
-use crate::query::QueryTrait;
+use crate::query::state::{Params, State};
+use crate::query::with_query::WithTrait;
+use crate::query::{PropsMatch, QueryTrait};
 
 /// Comparison operators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CompOper {
     /// Operation `=`.
     /// This means using the following construct in the query:
@@ -25,8 +32,94 @@ pub enum CompOper {
     /// This means using the following construct in the query:
     /// `n.{prop} <= {value}`
     LessEqual,
+    /// Operation `<>`.
+    /// This means using the following construct in the query:
+    /// `n.{prop} <> {value}`
+    NotEqual,
+    /// Operation `IN`, the value must be a list.
+    /// This means using the following construct in the query:
+    /// `n.{prop} IN [{value}, ...]`
+    In,
+    /// Operation `STARTS WITH`, the value must be a string.
+    /// This means using the following construct in the query:
+    /// `n.{prop} STARTS WITH {value}`
+    StartsWith,
+    /// Operation `ENDS WITH`, the value must be a string.
+    /// This means using the following construct in the query:
+    /// `n.{prop} ENDS WITH {value}`
+    EndsWith,
+    /// Operation `CONTAINS`, the value must be a string.
+    /// This means using the following construct in the query:
+    /// `n.{prop} CONTAINS {value}`
+    Contains,
+    /// Operation `=~`, the value must be a string with a regular expression.
+    /// This means using the following construct in the query:
+    /// `n.{prop} =~ {value}`
+    Regex,
+    /// Operation `IS NULL`, the value must be `PropType::Null`.
+    /// This means using the following construct in the query:
+    /// `n.{prop} IS NULL`
+    IsNull,
+    /// Operation `IS NOT NULL`, the value must be `PropType::Null`.
+    /// This means using the following construct in the query:
+    /// `n.{prop} IS NOT NULL`
+    IsNotNull,
+}
+
+impl CompOper {
+    /// Whether the operator is written without a right-hand side.
+    pub fn is_unary(&self) -> bool {
+        matches!(self, CompOper::IsNull | CompOper::IsNotNull)
+    }
+
+    /// Check that the value can be the right-hand side of the operator:
+    /// a list for `IN`, a string for the string operators and
+    /// `PropType::Null` for the null checks.
+    pub fn check(&self, value: &PropType) -> Result<(), OperandError> {
+        let valid = match self {
+            CompOper::In => matches!(value, PropType::Array(_)),
+            CompOper::StartsWith | CompOper::EndsWith | CompOper::Contains | CompOper::Regex => {
+                matches!(value, PropType::String(_))
+            }
+            CompOper::IsNull | CompOper::IsNotNull => *value == PropType::Null,
+            _ => true,
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err(OperandError {
+                op: *self,
+                value: value.to_prop(),
+            })
+        }
+    }
+}
+
+/// A value that can not be the right-hand side of a comparison operator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OperandError {
+    pub op: CompOper,
+    pub value: String,
+}
+
+impl std::fmt::Display for OperandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let expected = match self.op {
+            CompOper::In => "a list",
+            CompOper::IsNull | CompOper::IsNotNull => "no value",
+            _ => "a string",
+        };
+        write!(
+            f,
+            "operator `{}` expects {}, got {}",
+            self.op, expected, self.value
+        )
+    }
 }
 
+impl std::error::Error for OperandError {}
+
 impl std::fmt::Display for CompOper {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -35,6 +128,14 @@ impl std::fmt::Display for CompOper {
             CompOper::Less => write!(f, "<"),
             CompOper::MoreEqual => write!(f, ">="),
             CompOper::LessEqual => write!(f, "<="),
+            CompOper::NotEqual => write!(f, "<>"),
+            CompOper::In => write!(f, "IN"),
+            CompOper::StartsWith => write!(f, "STARTS WITH"),
+            CompOper::EndsWith => write!(f, "ENDS WITH"),
+            CompOper::Contains => write!(f, "CONTAINS"),
+            CompOper::Regex => write!(f, "=~"),
+            CompOper::IsNull => write!(f, "IS NULL"),
+            CompOper::IsNotNull => write!(f, "IS NOT NULL"),
         }
     }
 }
@@ -89,6 +190,16 @@ pub trait MatchActionTrait: 'static + ReturnTrait {
     /// Mostly used in internal methods that form shorter and more specialized functions
     /// or if you want to use types other than `String`, `Int` `Bool`.
     fn set_var(&self, nv: &str, prop: &str, value: PropType) -> Box<dyn ReturnTrait>;
+
+    /// **SET** of several properties at once, keeping the rest of them:
+    ///
+    /// `SET n += {name: '...', age: 0}`
+    fn set_props(&self, props: Props) -> Box<dyn ReturnTrait>;
+
+    /// **SET** of several properties at once with custom var:
+    ///
+    /// `SET n += {name: '...', age: 0}`
+    fn set_props_var(&self, nv: &str, props: Props) -> Box<dyn ReturnTrait>;
 }
 
 pub trait MatchConditionTrait: 'static + MatchActionTrait + QueryTrait {
@@ -214,6 +325,19 @@ pub trait MatchConditionTrait: 'static + MatchActionTrait + QueryTrait {
         op: CompOper,
         eq: PropType,
     ) -> Box<dyn MatchConditionTrait>;
+    /// **AND** with a list of values:
+    ///
+    /// `AND n.prop IN [...]`
+    fn and_in(&mut self, prop: &str, values: Vec<PropType>) -> Box<dyn MatchConditionTrait>;
+    /// **AND** with a list of values and custom var:
+    ///
+    /// `AND n.prop IN [...]`
+    fn and_in_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        values: Vec<PropType>,
+    ) -> Box<dyn MatchConditionTrait>;
 
     /// Pure **OR** query function.  
     ///
@@ -289,279 +413,712 @@ pub trait MatchConditionTrait: 'static + MatchActionTrait + QueryTrait {
         op: CompOper,
         eq: PropType,
     ) -> Box<dyn MatchConditionTrait>;
-}
-
-pub struct MatchConditionQuery {
-    nv: String,
-    state: String,
-}
-
-impl MatchConditionQuery {
-    pub fn new(nv: String, state: String) -> Self {
-        MatchConditionQuery { nv, state }
-    }
-}
-
-impl FinalizeTrait for MatchConditionQuery {
-    fn finalize(&self) -> String {
-        self.state.clone()
-    }
-}
-
-impl ReturnTrait for MatchConditionQuery {
-    fn r#return(&mut self, nv: &str) -> Box<dyn ReturnParamTrait> {
-        super::return_query::return_method(&self.state, vec![nv], None)
-    }
-
-    fn return_field(&mut self, nv: &str, field: &str) -> Box<dyn ReturnParamTrait> {
-        super::return_query::return_method(&self.state, vec![nv], Some(field))
-    }
-
-    fn return_many(&mut self, nvs: Vec<&str>) -> Box<dyn ReturnParamTrait> {
-        super::return_query::return_method(&self.state, nvs, None)
-    }
-}
-
-impl MatchActionTrait for MatchConditionQuery {
-    fn delete(&self) -> Box<dyn ReturnTrait> {
-        let state = format!(
-            "{prev_state}\nDELETE {node_var}\n",
-            prev_state = self.state,
-            node_var = self.nv
-        );
-        Box::new(ReturnQuery::new(state))
-    }
-
-    fn delete_detach(&self) -> Box<dyn ReturnTrait> {
-        let state = format!(
-            "{prev_state}\nDETACH DELETE {node_var}\n",
-            prev_state = self.state,
-            node_var = self.nv
-        );
-        Box::new(ReturnQuery::new(state))
-    }
-
-    fn set(&self, prop: &str, value: PropType) -> Box<dyn ReturnTrait> {
-        let state = format!(
-            "{prev_state}\nSET {node_var}.{prop_name}={value}",
-            prev_state = self.state,
-            node_var = self.nv,
-            prop_name = prop,
-            value = value.to_prop()
-        );
-        Box::new(ReturnQuery::new(state))
-    }
-
-    fn set_var(&self, nv: &str, prop: &str, value: PropType) -> Box<dyn ReturnTrait> {
-        let state = format!(
-            "{prev_state}\nSET {node_var}.{prop_name}={value}",
-            prev_state = self.state,
-            node_var = nv,
-            prop_name = prop,
-            value = value.to_prop()
-        );
-        Box::new(ReturnQuery::new(state))
-    }
+    /// **OR** with a list of values:
+    ///
+    /// `OR n.prop IN [...]`
+    fn or_in(&mut self, prop: &str, values: Vec<PropType>) -> Box<dyn MatchConditionTrait>;
+    /// **OR** with a list of values and custom var:
+    ///
+    /// `OR n.prop IN [...]`
+    fn or_in_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        values: Vec<PropType>,
+    ) -> Box<dyn MatchConditionTrait>;
 
-    #[cfg(feature = "templates")]
-    fn set_str(&self, prop: &str, value: &str) -> Box<dyn ReturnTrait> {
-        self.set(prop, PropType::str(value.to_string()))
-    }
+    /// **AND** with a whole condition expression:
+    ///
+    /// `... AND (n.x = 1 OR NOT n.y = 2)`
+    fn and_expr(&mut self, expr: Expr) -> Box<dyn MatchConditionTrait>;
 
-    #[cfg(feature = "templates")]
-    fn set_str_var(&self, nv: &str, prop: &str, value: &str) -> Box<dyn ReturnTrait> {
-        self.set_var(nv, prop, PropType::str(value.to_string()))
-    }
+    /// **OR** with a whole condition expression:
+    ///
+    /// `... OR n.x = 1 XOR n.y = 2`
+    fn or_expr(&mut self, expr: Expr) -> Box<dyn MatchConditionTrait>;
 
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `AND n.prop <> '...'`
     #[cfg(feature = "templates")]
-    fn set_int(&self, prop: &str, value: isize) -> Box<dyn ReturnTrait> {
-        self.set(prop, PropType::int(value))
-    }
-
+    fn and_ne_str(&mut self, prop: &str, value: &str) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `AND n.prop <> '...'`
     #[cfg(feature = "templates")]
-    fn set_int_var(&self, nv: &str, prop: &str, value: isize) -> Box<dyn ReturnTrait> {
-        self.set_var(nv, prop, PropType::int(value))
-    }
-
+    fn and_ne_str_var(&mut self, nv: &str, prop: &str, value: &str)
+        -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `AND n.prop <> 0`
     #[cfg(feature = "templates")]
-    fn set_bool(&self, prop: &str, value: bool) -> Box<dyn ReturnTrait> {
-        self.set(prop, PropType::Bool(value))
-    }
-
+    fn and_ne_int(&mut self, prop: &str, value: isize) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `AND n.prop <> 0`
     #[cfg(feature = "templates")]
-    fn set_bool_var(&self, nv: &str, prop: &str, value: bool) -> Box<dyn ReturnTrait> {
-        self.set_var(nv, prop, PropType::Bool(value))
-    }
-}
-
-impl MatchConditionTrait for MatchConditionQuery {
-    fn and(&mut self, prop: &str, op: CompOper, eq: PropType) -> Box<dyn MatchConditionTrait> {
-        let state = format!(
-            "{prev_state} AND {node_var}.{prop_name} {operator} {value} ",
-            prev_state = self.state,
-            node_var = self.nv,
-            prop_name = prop,
-            operator = op.to_string(),
-            value = eq.to_prop()
-        );
-
-        Box::new(Self::new(self.nv.clone(), state))
-    }
-
-    fn or(&mut self, prop: &str, op: CompOper, eq: PropType) -> Box<dyn MatchConditionTrait> {
-        let state = format!(
-            "{prev_state} OR {node_var}.{prop_name} {operator} {value} ",
-            prev_state = self.state,
-            node_var = self.nv,
-            prop_name = prop,
-            operator = op.to_string(),
-            value = eq.to_prop()
-        );
-        Box::new(Self::new(self.nv.clone(), state))
-    }
-
-    fn and_var(
-        &mut self,
-        nv: &str,
-        prop: &str,
-        op: CompOper,
-        eq: PropType,
-    ) -> Box<dyn MatchConditionTrait> {
-        let state = format!(
-            "{prev_state} AND {node_var}.{prop_name} {operator} {value} ",
-            prev_state = self.state,
-            node_var = nv,
-            prop_name = prop,
-            operator = op.to_string(),
-            value = eq.to_prop()
-        );
-
-        Box::new(Self::new(self.nv.clone(), state))
-    }
-
-    fn or_var(
+    fn and_ne_int_var(
         &mut self,
         nv: &str,
         prop: &str,
-        op: CompOper,
-        eq: PropType,
-    ) -> Box<dyn MatchConditionTrait> {
-        let state = format!(
-            "{prev_state} OR {node_var}.{prop_name} {operator} {value} ",
-            prev_state = self.state,
-            node_var = nv,
-            prop_name = prop,
-            operator = op.to_string(),
-            value = eq.to_prop()
-        );
-        Box::new(Self::new(self.nv.clone(), state))
-    }
-
+        value: isize,
+    ) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `AND n.prop <> true`
     #[cfg(feature = "templates")]
-    fn and_eq_str(&mut self, prop: &str, value: &str) -> Box<dyn MatchConditionTrait> {
-        self.and(prop, CompOper::Equal, PropType::str(value.to_string()))
-    }
-
+    fn and_ne_bool(&mut self, prop: &str, value: bool) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `AND n.prop <> true`
     #[cfg(feature = "templates")]
-    fn and_eq_str_var(
+    fn and_ne_bool_var(
         &mut self,
         nv: &str,
         prop: &str,
-        value: &str,
-    ) -> Box<dyn MatchConditionTrait> {
-        self.and_var(nv, prop, CompOper::Equal, PropType::str(value.to_string()))
-    }
-
+        value: bool,
+    ) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `AND n.prop IN ['...', '...']`
     #[cfg(feature = "templates")]
-    fn and_eq_int(&mut self, prop: &str, value: isize) -> Box<dyn MatchConditionTrait> {
-        self.and(prop, CompOper::Equal, PropType::int(value))
-    }
-
+    fn and_in_str(&mut self, prop: &str, values: Vec<&str>) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `AND n.prop IN ['...', '...']`
     #[cfg(feature = "templates")]
-    fn and_eq_int_var(
+    fn and_in_str_var(
         &mut self,
         nv: &str,
         prop: &str,
-        value: isize,
-    ) -> Box<dyn MatchConditionTrait> {
-        self.and_var(nv, prop, CompOper::Equal, PropType::int(value))
-    }
-
+        values: Vec<&str>,
+    ) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `AND n.prop IN [0, 1]`
     #[cfg(feature = "templates")]
-    fn and_eq_bool(&mut self, prop: &str, value: bool) -> Box<dyn MatchConditionTrait> {
-        self.and(prop, CompOper::Equal, PropType::Bool(value))
-    }
-
+    fn and_in_int(&mut self, prop: &str, values: Vec<isize>) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `AND n.prop IN [0, 1]`
     #[cfg(feature = "templates")]
-    fn and_eq_bool_var(
+    fn and_in_int_var(
         &mut self,
         nv: &str,
         prop: &str,
-        value: bool,
-    ) -> Box<dyn MatchConditionTrait> {
-        self.and_var(nv, prop, CompOper::Equal, PropType::Bool(value))
-    }
-
+        values: Vec<isize>,
+    ) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `AND n.prop STARTS WITH '...'`
     #[cfg(feature = "templates")]
-    fn and_more_int(&mut self, prop: &str, value: isize) -> Box<dyn MatchConditionTrait> {
-        self.and(prop, CompOper::More, PropType::int(value))
-    }
-
+    fn and_starts_with(&mut self, prop: &str, value: &str) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `AND n.prop STARTS WITH '...'`
     #[cfg(feature = "templates")]
-    fn and_more_int_var(
+    fn and_starts_with_var(
         &mut self,
         nv: &str,
         prop: &str,
-        value: isize,
-    ) -> Box<dyn MatchConditionTrait> {
-        self.and_var(nv, prop, CompOper::More, PropType::int(value))
-    }
-
+        value: &str,
+    ) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `AND n.prop ENDS WITH '...'`
     #[cfg(feature = "templates")]
-    fn and_less_int(&mut self, prop: &str, value: isize) -> Box<dyn MatchConditionTrait> {
-        self.and(prop, CompOper::Less, PropType::int(value))
-    }
-
+    fn and_ends_with(&mut self, prop: &str, value: &str) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `AND n.prop ENDS WITH '...'`
     #[cfg(feature = "templates")]
-    fn and_less_int_var(
+    fn and_ends_with_var(
         &mut self,
         nv: &str,
         prop: &str,
-        value: isize,
-    ) -> Box<dyn MatchConditionTrait> {
-        self.and_var(nv, prop, CompOper::Less, PropType::int(value))
-    }
-
+        value: &str,
+    ) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `AND n.prop CONTAINS '...'`
     #[cfg(feature = "templates")]
-    fn and_moreq_int(&mut self, prop: &str, value: isize) -> Box<dyn MatchConditionTrait> {
-        self.and(prop, CompOper::MoreEqual, PropType::int(value))
-    }
-
+    fn and_contains(&mut self, prop: &str, value: &str) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `AND n.prop CONTAINS '...'`
     #[cfg(feature = "templates")]
-    fn and_moreq_int_var(
+    fn and_contains_var(
         &mut self,
         nv: &str,
         prop: &str,
-        value: isize,
-    ) -> Box<dyn MatchConditionTrait> {
-        self.and_var(nv, prop, CompOper::MoreEqual, PropType::int(value))
-    }
-
+        value: &str,
+    ) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `AND n.prop =~ '...'`
     #[cfg(feature = "templates")]
-    fn and_leseq_int(&mut self, prop: &str, value: isize) -> Box<dyn MatchConditionTrait> {
-        self.and(prop, CompOper::LessEqual, PropType::int(value))
-    }
-
+    fn and_regex(&mut self, prop: &str, pattern: &str) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `AND n.prop =~ '...'`
     #[cfg(feature = "templates")]
-    fn and_leseq_int_var(
+    fn and_regex_var(
         &mut self,
         nv: &str,
         prop: &str,
-        value: isize,
-    ) -> Box<dyn MatchConditionTrait> {
-        self.and_var(nv, prop, CompOper::LessEqual, PropType::int(value))
-    }
-
+        pattern: &str,
+    ) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `AND n.prop IS NULL`
+    fn and_is_null(&mut self, prop: &str) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `AND n.prop IS NULL`
+    fn and_is_null_var(&mut self, nv: &str, prop: &str) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `AND n.prop IS NOT NULL`
+    fn and_is_not_null(&mut self, prop: &str) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `AND n.prop IS NOT NULL`
+    fn and_is_not_null_var(&mut self, nv: &str, prop: &str) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `AND point.distance(n.prop, point({...})) <= ...`
+    #[cfg(feature = "templates")]
+    fn and_within_distance(
+        &mut self,
+        prop: &str,
+        point: Point,
+        distance: f64,
+    ) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `AND point.distance(n.prop, point({...})) <= ...`
+    #[cfg(feature = "templates")]
+    fn and_within_distance_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        point: Point,
+        distance: f64,
+    ) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `AND point.withinBBox(n.prop, point({...}), point({...}))`
+    #[cfg(feature = "templates")]
+    fn and_within_bbox(
+        &mut self,
+        prop: &str,
+        lower_left: Point,
+        upper_right: Point,
+    ) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `AND point.withinBBox(n.prop, point({...}), point({...}))`
+    #[cfg(feature = "templates")]
+    fn and_within_bbox_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        lower_left: Point,
+        upper_right: Point,
+    ) -> Box<dyn MatchConditionTrait>;
+
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `OR n.prop <> '...'`
+    #[cfg(feature = "templates")]
+    fn or_ne_str(&mut self, prop: &str, value: &str) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `OR n.prop <> '...'`
+    #[cfg(feature = "templates")]
+    fn or_ne_str_var(&mut self, nv: &str, prop: &str, value: &str) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `OR n.prop <> 0`
+    #[cfg(feature = "templates")]
+    fn or_ne_int(&mut self, prop: &str, value: isize) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `OR n.prop <> 0`
+    #[cfg(feature = "templates")]
+    fn or_ne_int_var(&mut self, nv: &str, prop: &str, value: isize)
+        -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `OR n.prop <> true`
+    #[cfg(feature = "templates")]
+    fn or_ne_bool(&mut self, prop: &str, value: bool) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `OR n.prop <> true`
+    #[cfg(feature = "templates")]
+    fn or_ne_bool_var(&mut self, nv: &str, prop: &str, value: bool)
+        -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `OR n.prop IN ['...', '...']`
+    #[cfg(feature = "templates")]
+    fn or_in_str(&mut self, prop: &str, values: Vec<&str>) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `OR n.prop IN ['...', '...']`
+    #[cfg(feature = "templates")]
+    fn or_in_str_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        values: Vec<&str>,
+    ) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `OR n.prop IN [0, 1]`
+    #[cfg(feature = "templates")]
+    fn or_in_int(&mut self, prop: &str, values: Vec<isize>) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `OR n.prop IN [0, 1]`
+    #[cfg(feature = "templates")]
+    fn or_in_int_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        values: Vec<isize>,
+    ) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `OR n.prop STARTS WITH '...'`
+    #[cfg(feature = "templates")]
+    fn or_starts_with(&mut self, prop: &str, value: &str) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `OR n.prop STARTS WITH '...'`
+    #[cfg(feature = "templates")]
+    fn or_starts_with_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        value: &str,
+    ) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `OR n.prop ENDS WITH '...'`
+    #[cfg(feature = "templates")]
+    fn or_ends_with(&mut self, prop: &str, value: &str) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `OR n.prop ENDS WITH '...'`
+    #[cfg(feature = "templates")]
+    fn or_ends_with_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        value: &str,
+    ) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `OR n.prop CONTAINS '...'`
+    #[cfg(feature = "templates")]
+    fn or_contains(&mut self, prop: &str, value: &str) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `OR n.prop CONTAINS '...'`
+    #[cfg(feature = "templates")]
+    fn or_contains_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        value: &str,
+    ) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `OR n.prop =~ '...'`
+    #[cfg(feature = "templates")]
+    fn or_regex(&mut self, prop: &str, pattern: &str) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `OR n.prop =~ '...'`
+    #[cfg(feature = "templates")]
+    fn or_regex_var(&mut self, nv: &str, prop: &str, pattern: &str)
+        -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `OR n.prop IS NULL`
+    fn or_is_null(&mut self, prop: &str) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `OR n.prop IS NULL`
+    fn or_is_null_var(&mut self, nv: &str, prop: &str) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `OR n.prop IS NOT NULL`
+    fn or_is_not_null(&mut self, prop: &str) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `OR n.prop IS NOT NULL`
+    fn or_is_not_null_var(&mut self, nv: &str, prop: &str) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `OR point.distance(n.prop, point({...})) <= ...`
+    #[cfg(feature = "templates")]
+    fn or_within_distance(
+        &mut self,
+        prop: &str,
+        point: Point,
+        distance: f64,
+    ) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `OR point.distance(n.prop, point({...})) <= ...`
+    #[cfg(feature = "templates")]
+    fn or_within_distance_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        point: Point,
+        distance: f64,
+    ) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `OR point.withinBBox(n.prop, point({...}), point({...}))`
+    #[cfg(feature = "templates")]
+    fn or_within_bbox(
+        &mut self,
+        prop: &str,
+        lower_left: Point,
+        upper_right: Point,
+    ) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `OR point.withinBBox(n.prop, point({...}), point({...}))`
+    #[cfg(feature = "templates")]
+    fn or_within_bbox_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        lower_left: Point,
+        upper_right: Point,
+    ) -> Box<dyn MatchConditionTrait>;
+}
+
+pub struct MatchConditionQuery {
+    nv: String,
+    state: State,
+}
+
+impl MatchConditionQuery {
+    pub fn new(nv: String, state: State) -> Self {
+        MatchConditionQuery { nv, state }
+    }
+}
+
+impl FinalizeTrait for MatchConditionQuery {
+    fn finalize(&self) -> String {
+        self.state.query()
+    }
+
+    fn finalize_with_params(&self) -> (String, Params) {
+        self.state.render()
+    }
+
+    fn finalize_state(&self) -> State {
+        self.state.clone()
+    }
+}
+
+impl ReturnTrait for MatchConditionQuery {
+    fn r#return(&mut self, nv: &str) -> Box<dyn ReturnParamTrait> {
+        super::return_query::return_method(&self.state, vec![nv], None)
+    }
+
+    fn return_field(&mut self, nv: &str, field: &str) -> Box<dyn ReturnParamTrait> {
+        super::return_query::return_method(&self.state, vec![nv], Some(field))
+    }
+
+    fn return_many(&mut self, nvs: Vec<&str>) -> Box<dyn ReturnParamTrait> {
+        super::return_query::return_method(&self.state, nvs, None)
+    }
+
+    fn return_items(&mut self, items: Vec<ReturnItem>) -> Box<dyn ReturnParamTrait> {
+        super::return_query::return_items_method(&self.state, items)
+    }
+
+    fn return_all(&mut self) -> Box<dyn ReturnParamTrait> {
+        super::return_query::return_items_method(&self.state, vec![Expr::all().into()])
+    }
+
+    fn with(&mut self, items: Vec<ReturnItem>) -> Box<dyn WithTrait> {
+        super::with_query::with_method(&self.state, items)
+    }
+}
+
+impl MatchActionTrait for MatchConditionQuery {
+    fn delete(&self) -> Box<dyn ReturnTrait> {
+        let mut state = self.state.clone();
+        state.push(Clause::Delete {
+            detach: false,
+            vars: vec![self.nv.clone()],
+        });
+        Box::new(ReturnQuery::new(state))
+    }
+
+    fn delete_detach(&self) -> Box<dyn ReturnTrait> {
+        let mut state = self.state.clone();
+        state.push(Clause::Delete {
+            detach: true,
+            vars: vec![self.nv.clone()],
+        });
+        Box::new(ReturnQuery::new(state))
+    }
+
+    fn set(&self, prop: &str, value: PropType) -> Box<dyn ReturnTrait> {
+        let mut state = self.state.clone();
+        state.push(Clause::Set(vec![SetItem::Property {
+            var: self.nv.clone(),
+            key: prop.to_string(),
+            value: Expr::Value(value),
+        }]));
+        Box::new(ReturnQuery::new(state))
+    }
+
+    fn set_var(&self, nv: &str, prop: &str, value: PropType) -> Box<dyn ReturnTrait> {
+        let mut state = self.state.clone();
+        state.push(Clause::Set(vec![SetItem::Property {
+            var: nv.to_string(),
+            key: prop.to_string(),
+            value: Expr::Value(value),
+        }]));
+        Box::new(ReturnQuery::new(state))
+    }
+
+    fn set_props(&self, props: Props) -> Box<dyn ReturnTrait> {
+        self.set_props_var(&self.nv, props)
+    }
+
+    fn set_props_var(&self, nv: &str, props: Props) -> Box<dyn ReturnTrait> {
+        let mut state = self.state.clone();
+        state.push(Clause::Set(vec![SetItem::Extend {
+            var: nv.to_string(),
+            value: Expr::Value(PropType::Map(props)),
+        }]));
+        Box::new(ReturnQuery::new(state))
+    }
+
+    #[cfg(feature = "templates")]
+    fn set_str(&self, prop: &str, value: &str) -> Box<dyn ReturnTrait> {
+        self.set(prop, PropType::str(value.to_string()))
+    }
+
+    #[cfg(feature = "templates")]
+    fn set_str_var(&self, nv: &str, prop: &str, value: &str) -> Box<dyn ReturnTrait> {
+        self.set_var(nv, prop, PropType::str(value.to_string()))
+    }
+
+    #[cfg(feature = "templates")]
+    fn set_int(&self, prop: &str, value: isize) -> Box<dyn ReturnTrait> {
+        self.set(prop, PropType::int(value))
+    }
+
+    #[cfg(feature = "templates")]
+    fn set_int_var(&self, nv: &str, prop: &str, value: isize) -> Box<dyn ReturnTrait> {
+        self.set_var(nv, prop, PropType::int(value))
+    }
+
+    #[cfg(feature = "templates")]
+    fn set_bool(&self, prop: &str, value: bool) -> Box<dyn ReturnTrait> {
+        self.set(prop, PropType::Bool(value))
+    }
+
+    #[cfg(feature = "templates")]
+    fn set_bool_var(&self, nv: &str, prop: &str, value: bool) -> Box<dyn ReturnTrait> {
+        self.set_var(nv, prop, PropType::Bool(value))
+    }
+}
+
+impl MatchConditionTrait for MatchConditionQuery {
+    fn and(&mut self, prop: &str, op: CompOper, eq: PropType) -> Box<dyn MatchConditionTrait> {
+        let mut state = self.state.clone();
+        add_condition(&mut state, Expr::cond(&self.nv, prop, op, eq), false);
+        Box::new(Self::new(self.nv.clone(), state))
+    }
+
+    fn or(&mut self, prop: &str, op: CompOper, eq: PropType) -> Box<dyn MatchConditionTrait> {
+        let mut state = self.state.clone();
+        add_condition(&mut state, Expr::cond(&self.nv, prop, op, eq), true);
+        Box::new(Self::new(self.nv.clone(), state))
+    }
+
+    fn and_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        op: CompOper,
+        eq: PropType,
+    ) -> Box<dyn MatchConditionTrait> {
+        let mut state = self.state.clone();
+        add_condition(&mut state, Expr::cond(nv, prop, op, eq), false);
+        Box::new(Self::new(self.nv.clone(), state))
+    }
+
+    fn and_in(&mut self, prop: &str, values: Vec<PropType>) -> Box<dyn MatchConditionTrait> {
+        self.and(prop, CompOper::In, PropType::Array(values))
+    }
+
+    fn and_in_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        values: Vec<PropType>,
+    ) -> Box<dyn MatchConditionTrait> {
+        self.and_var(nv, prop, CompOper::In, PropType::Array(values))
+    }
+
+    fn or_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        op: CompOper,
+        eq: PropType,
+    ) -> Box<dyn MatchConditionTrait> {
+        let mut state = self.state.clone();
+        add_condition(&mut state, Expr::cond(nv, prop, op, eq), true);
+        Box::new(Self::new(self.nv.clone(), state))
+    }
+
+    fn or_in(&mut self, prop: &str, values: Vec<PropType>) -> Box<dyn MatchConditionTrait> {
+        self.or(prop, CompOper::In, PropType::Array(values))
+    }
+
+    fn or_in_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        values: Vec<PropType>,
+    ) -> Box<dyn MatchConditionTrait> {
+        self.or_var(nv, prop, CompOper::In, PropType::Array(values))
+    }
+
+    fn and_expr(&mut self, expr: Expr) -> Box<dyn MatchConditionTrait> {
+        let mut state = self.state.clone();
+        add_condition(&mut state, expr, false);
+        Box::new(Self::new(self.nv.clone(), state))
+    }
+
+    fn or_expr(&mut self, expr: Expr) -> Box<dyn MatchConditionTrait> {
+        let mut state = self.state.clone();
+        add_condition(&mut state, expr, true);
+        Box::new(Self::new(self.nv.clone(), state))
+    }
+
+    #[cfg(feature = "templates")]
+    fn and_eq_str(&mut self, prop: &str, value: &str) -> Box<dyn MatchConditionTrait> {
+        self.and(prop, CompOper::Equal, PropType::str(value.to_string()))
+    }
+
+    #[cfg(feature = "templates")]
+    fn and_eq_str_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        value: &str,
+    ) -> Box<dyn MatchConditionTrait> {
+        self.and_var(nv, prop, CompOper::Equal, PropType::str(value.to_string()))
+    }
+
+    #[cfg(feature = "templates")]
+    fn and_eq_int(&mut self, prop: &str, value: isize) -> Box<dyn MatchConditionTrait> {
+        self.and(prop, CompOper::Equal, PropType::int(value))
+    }
+
+    #[cfg(feature = "templates")]
+    fn and_eq_int_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        value: isize,
+    ) -> Box<dyn MatchConditionTrait> {
+        self.and_var(nv, prop, CompOper::Equal, PropType::int(value))
+    }
+
+    #[cfg(feature = "templates")]
+    fn and_eq_bool(&mut self, prop: &str, value: bool) -> Box<dyn MatchConditionTrait> {
+        self.and(prop, CompOper::Equal, PropType::Bool(value))
+    }
+
+    #[cfg(feature = "templates")]
+    fn and_eq_bool_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        value: bool,
+    ) -> Box<dyn MatchConditionTrait> {
+        self.and_var(nv, prop, CompOper::Equal, PropType::Bool(value))
+    }
+
+    #[cfg(feature = "templates")]
+    fn and_more_int(&mut self, prop: &str, value: isize) -> Box<dyn MatchConditionTrait> {
+        self.and(prop, CompOper::More, PropType::int(value))
+    }
+
+    #[cfg(feature = "templates")]
+    fn and_more_int_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        value: isize,
+    ) -> Box<dyn MatchConditionTrait> {
+        self.and_var(nv, prop, CompOper::More, PropType::int(value))
+    }
+
+    #[cfg(feature = "templates")]
+    fn and_less_int(&mut self, prop: &str, value: isize) -> Box<dyn MatchConditionTrait> {
+        self.and(prop, CompOper::Less, PropType::int(value))
+    }
+
+    #[cfg(feature = "templates")]
+    fn and_less_int_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        value: isize,
+    ) -> Box<dyn MatchConditionTrait> {
+        self.and_var(nv, prop, CompOper::Less, PropType::int(value))
+    }
+
+    #[cfg(feature = "templates")]
+    fn and_moreq_int(&mut self, prop: &str, value: isize) -> Box<dyn MatchConditionTrait> {
+        self.and(prop, CompOper::MoreEqual, PropType::int(value))
+    }
+
+    #[cfg(feature = "templates")]
+    fn and_moreq_int_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        value: isize,
+    ) -> Box<dyn MatchConditionTrait> {
+        self.and_var(nv, prop, CompOper::MoreEqual, PropType::int(value))
+    }
+
+    #[cfg(feature = "templates")]
+    fn and_leseq_int(&mut self, prop: &str, value: isize) -> Box<dyn MatchConditionTrait> {
+        self.and(prop, CompOper::LessEqual, PropType::int(value))
+    }
+
+    #[cfg(feature = "templates")]
+    fn and_leseq_int_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        value: isize,
+    ) -> Box<dyn MatchConditionTrait> {
+        self.and_var(nv, prop, CompOper::LessEqual, PropType::int(value))
+    }
+
     #[cfg(feature = "templates")]
     fn or_eq_str(&mut self, prop: &str, value: &str) -> Box<dyn MatchConditionTrait> {
         self.or(prop, CompOper::Equal, PropType::str(value.to_string()))
@@ -627,49 +1184,512 @@ impl MatchConditionTrait for MatchConditionQuery {
         &mut self,
         nv: &str,
         prop: &str,
-        value: isize,
+        value: isize,
+    ) -> Box<dyn MatchConditionTrait> {
+        self.or_var(nv, prop, CompOper::Less, PropType::int(value))
+    }
+
+    #[cfg(feature = "templates")]
+    fn or_moreq_int(&mut self, prop: &str, value: isize) -> Box<dyn MatchConditionTrait> {
+        self.or(prop, CompOper::MoreEqual, PropType::int(value))
+    }
+
+    #[cfg(feature = "templates")]
+    fn or_moreq_int_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        value: isize,
+    ) -> Box<dyn MatchConditionTrait> {
+        self.or_var(nv, prop, CompOper::MoreEqual, PropType::int(value))
+    }
+
+    #[cfg(feature = "templates")]
+    fn or_leseq_int(&mut self, prop: &str, value: isize) -> Box<dyn MatchConditionTrait> {
+        self.or(prop, CompOper::LessEqual, PropType::int(value))
+    }
+
+    #[cfg(feature = "templates")]
+    fn or_leseq_int_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        value: isize,
+    ) -> Box<dyn MatchConditionTrait> {
+        self.or_var(nv, prop, CompOper::LessEqual, PropType::int(value))
+    }
+
+    /* Other operators */
+
+    #[cfg(feature = "templates")]
+    fn and_ne_str(&mut self, prop: &str, value: &str) -> Box<dyn MatchConditionTrait> {
+        self.and(prop, CompOper::NotEqual, PropType::str(value.to_string()))
+    }
+    #[cfg(feature = "templates")]
+    fn and_ne_str_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        value: &str,
+    ) -> Box<dyn MatchConditionTrait> {
+        self.and_var(
+            nv,
+            prop,
+            CompOper::NotEqual,
+            PropType::str(value.to_string()),
+        )
+    }
+    #[cfg(feature = "templates")]
+    fn and_ne_int(&mut self, prop: &str, value: isize) -> Box<dyn MatchConditionTrait> {
+        self.and(prop, CompOper::NotEqual, PropType::int(value))
+    }
+    #[cfg(feature = "templates")]
+    fn and_ne_int_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        value: isize,
+    ) -> Box<dyn MatchConditionTrait> {
+        self.and_var(nv, prop, CompOper::NotEqual, PropType::int(value))
+    }
+    #[cfg(feature = "templates")]
+    fn and_ne_bool(&mut self, prop: &str, value: bool) -> Box<dyn MatchConditionTrait> {
+        self.and(prop, CompOper::NotEqual, PropType::Bool(value))
+    }
+    #[cfg(feature = "templates")]
+    fn and_ne_bool_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        value: bool,
+    ) -> Box<dyn MatchConditionTrait> {
+        self.and_var(nv, prop, CompOper::NotEqual, PropType::Bool(value))
+    }
+    #[cfg(feature = "templates")]
+    fn and_in_str(&mut self, prop: &str, values: Vec<&str>) -> Box<dyn MatchConditionTrait> {
+        self.and(
+            prop,
+            CompOper::In,
+            PropType::Array(
+                values
+                    .into_iter()
+                    .map(|v| PropType::str(v.to_string()))
+                    .collect(),
+            ),
+        )
+    }
+    #[cfg(feature = "templates")]
+    fn and_in_str_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        values: Vec<&str>,
+    ) -> Box<dyn MatchConditionTrait> {
+        self.and_var(
+            nv,
+            prop,
+            CompOper::In,
+            PropType::Array(
+                values
+                    .into_iter()
+                    .map(|v| PropType::str(v.to_string()))
+                    .collect(),
+            ),
+        )
+    }
+    #[cfg(feature = "templates")]
+    fn and_in_int(&mut self, prop: &str, values: Vec<isize>) -> Box<dyn MatchConditionTrait> {
+        self.and(
+            prop,
+            CompOper::In,
+            PropType::Array(values.into_iter().map(PropType::int).collect()),
+        )
+    }
+    #[cfg(feature = "templates")]
+    fn and_in_int_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        values: Vec<isize>,
+    ) -> Box<dyn MatchConditionTrait> {
+        self.and_var(
+            nv,
+            prop,
+            CompOper::In,
+            PropType::Array(values.into_iter().map(PropType::int).collect()),
+        )
+    }
+    #[cfg(feature = "templates")]
+    fn and_starts_with(&mut self, prop: &str, value: &str) -> Box<dyn MatchConditionTrait> {
+        self.and(prop, CompOper::StartsWith, PropType::str(value.to_string()))
+    }
+    #[cfg(feature = "templates")]
+    fn and_starts_with_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        value: &str,
+    ) -> Box<dyn MatchConditionTrait> {
+        self.and_var(
+            nv,
+            prop,
+            CompOper::StartsWith,
+            PropType::str(value.to_string()),
+        )
+    }
+    #[cfg(feature = "templates")]
+    fn and_ends_with(&mut self, prop: &str, value: &str) -> Box<dyn MatchConditionTrait> {
+        self.and(prop, CompOper::EndsWith, PropType::str(value.to_string()))
+    }
+    #[cfg(feature = "templates")]
+    fn and_ends_with_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        value: &str,
+    ) -> Box<dyn MatchConditionTrait> {
+        self.and_var(
+            nv,
+            prop,
+            CompOper::EndsWith,
+            PropType::str(value.to_string()),
+        )
+    }
+    #[cfg(feature = "templates")]
+    fn and_contains(&mut self, prop: &str, value: &str) -> Box<dyn MatchConditionTrait> {
+        self.and(prop, CompOper::Contains, PropType::str(value.to_string()))
+    }
+    #[cfg(feature = "templates")]
+    fn and_contains_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        value: &str,
+    ) -> Box<dyn MatchConditionTrait> {
+        self.and_var(
+            nv,
+            prop,
+            CompOper::Contains,
+            PropType::str(value.to_string()),
+        )
+    }
+    #[cfg(feature = "templates")]
+    fn and_regex(&mut self, prop: &str, pattern: &str) -> Box<dyn MatchConditionTrait> {
+        self.and(prop, CompOper::Regex, PropType::str(pattern.to_string()))
+    }
+    #[cfg(feature = "templates")]
+    fn and_regex_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        pattern: &str,
+    ) -> Box<dyn MatchConditionTrait> {
+        self.and_var(
+            nv,
+            prop,
+            CompOper::Regex,
+            PropType::str(pattern.to_string()),
+        )
+    }
+    fn and_is_null(&mut self, prop: &str) -> Box<dyn MatchConditionTrait> {
+        self.and(prop, CompOper::IsNull, PropType::Null)
+    }
+    fn and_is_null_var(&mut self, nv: &str, prop: &str) -> Box<dyn MatchConditionTrait> {
+        self.and_var(nv, prop, CompOper::IsNull, PropType::Null)
+    }
+    fn and_is_not_null(&mut self, prop: &str) -> Box<dyn MatchConditionTrait> {
+        self.and(prop, CompOper::IsNotNull, PropType::Null)
+    }
+    fn and_is_not_null_var(&mut self, nv: &str, prop: &str) -> Box<dyn MatchConditionTrait> {
+        self.and_var(nv, prop, CompOper::IsNotNull, PropType::Null)
+    }
+
+    #[cfg(feature = "templates")]
+    fn and_within_distance(
+        &mut self,
+        prop: &str,
+        point: Point,
+        distance: f64,
+    ) -> Box<dyn MatchConditionTrait> {
+        let nv = self.nv.clone();
+        self.and_within_distance_var(&nv, prop, point, distance)
+    }
+    #[cfg(feature = "templates")]
+    fn and_within_distance_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        point: Point,
+        distance: f64,
+    ) -> Box<dyn MatchConditionTrait> {
+        self.and_expr(within_distance(nv, prop, point, distance))
+    }
+    #[cfg(feature = "templates")]
+    fn and_within_bbox(
+        &mut self,
+        prop: &str,
+        lower_left: Point,
+        upper_right: Point,
+    ) -> Box<dyn MatchConditionTrait> {
+        let nv = self.nv.clone();
+        self.and_within_bbox_var(&nv, prop, lower_left, upper_right)
+    }
+    #[cfg(feature = "templates")]
+    fn and_within_bbox_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        lower_left: Point,
+        upper_right: Point,
+    ) -> Box<dyn MatchConditionTrait> {
+        self.and_expr(within_bbox(nv, prop, lower_left, upper_right))
+    }
+
+    #[cfg(feature = "templates")]
+    fn or_ne_str(&mut self, prop: &str, value: &str) -> Box<dyn MatchConditionTrait> {
+        self.or(prop, CompOper::NotEqual, PropType::str(value.to_string()))
+    }
+    #[cfg(feature = "templates")]
+    fn or_ne_str_var(&mut self, nv: &str, prop: &str, value: &str) -> Box<dyn MatchConditionTrait> {
+        self.or_var(
+            nv,
+            prop,
+            CompOper::NotEqual,
+            PropType::str(value.to_string()),
+        )
+    }
+    #[cfg(feature = "templates")]
+    fn or_ne_int(&mut self, prop: &str, value: isize) -> Box<dyn MatchConditionTrait> {
+        self.or(prop, CompOper::NotEqual, PropType::int(value))
+    }
+    #[cfg(feature = "templates")]
+    fn or_ne_int_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        value: isize,
+    ) -> Box<dyn MatchConditionTrait> {
+        self.or_var(nv, prop, CompOper::NotEqual, PropType::int(value))
+    }
+    #[cfg(feature = "templates")]
+    fn or_ne_bool(&mut self, prop: &str, value: bool) -> Box<dyn MatchConditionTrait> {
+        self.or(prop, CompOper::NotEqual, PropType::Bool(value))
+    }
+    #[cfg(feature = "templates")]
+    fn or_ne_bool_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        value: bool,
+    ) -> Box<dyn MatchConditionTrait> {
+        self.or_var(nv, prop, CompOper::NotEqual, PropType::Bool(value))
+    }
+    #[cfg(feature = "templates")]
+    fn or_in_str(&mut self, prop: &str, values: Vec<&str>) -> Box<dyn MatchConditionTrait> {
+        self.or(
+            prop,
+            CompOper::In,
+            PropType::Array(
+                values
+                    .into_iter()
+                    .map(|v| PropType::str(v.to_string()))
+                    .collect(),
+            ),
+        )
+    }
+    #[cfg(feature = "templates")]
+    fn or_in_str_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        values: Vec<&str>,
+    ) -> Box<dyn MatchConditionTrait> {
+        self.or_var(
+            nv,
+            prop,
+            CompOper::In,
+            PropType::Array(
+                values
+                    .into_iter()
+                    .map(|v| PropType::str(v.to_string()))
+                    .collect(),
+            ),
+        )
+    }
+    #[cfg(feature = "templates")]
+    fn or_in_int(&mut self, prop: &str, values: Vec<isize>) -> Box<dyn MatchConditionTrait> {
+        self.or(
+            prop,
+            CompOper::In,
+            PropType::Array(values.into_iter().map(PropType::int).collect()),
+        )
+    }
+    #[cfg(feature = "templates")]
+    fn or_in_int_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        values: Vec<isize>,
+    ) -> Box<dyn MatchConditionTrait> {
+        self.or_var(
+            nv,
+            prop,
+            CompOper::In,
+            PropType::Array(values.into_iter().map(PropType::int).collect()),
+        )
+    }
+    #[cfg(feature = "templates")]
+    fn or_starts_with(&mut self, prop: &str, value: &str) -> Box<dyn MatchConditionTrait> {
+        self.or(prop, CompOper::StartsWith, PropType::str(value.to_string()))
+    }
+    #[cfg(feature = "templates")]
+    fn or_starts_with_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        value: &str,
+    ) -> Box<dyn MatchConditionTrait> {
+        self.or_var(
+            nv,
+            prop,
+            CompOper::StartsWith,
+            PropType::str(value.to_string()),
+        )
+    }
+    #[cfg(feature = "templates")]
+    fn or_ends_with(&mut self, prop: &str, value: &str) -> Box<dyn MatchConditionTrait> {
+        self.or(prop, CompOper::EndsWith, PropType::str(value.to_string()))
+    }
+    #[cfg(feature = "templates")]
+    fn or_ends_with_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        value: &str,
+    ) -> Box<dyn MatchConditionTrait> {
+        self.or_var(
+            nv,
+            prop,
+            CompOper::EndsWith,
+            PropType::str(value.to_string()),
+        )
+    }
+    #[cfg(feature = "templates")]
+    fn or_contains(&mut self, prop: &str, value: &str) -> Box<dyn MatchConditionTrait> {
+        self.or(prop, CompOper::Contains, PropType::str(value.to_string()))
+    }
+    #[cfg(feature = "templates")]
+    fn or_contains_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        value: &str,
+    ) -> Box<dyn MatchConditionTrait> {
+        self.or_var(
+            nv,
+            prop,
+            CompOper::Contains,
+            PropType::str(value.to_string()),
+        )
+    }
+    #[cfg(feature = "templates")]
+    fn or_regex(&mut self, prop: &str, pattern: &str) -> Box<dyn MatchConditionTrait> {
+        self.or(prop, CompOper::Regex, PropType::str(pattern.to_string()))
+    }
+    #[cfg(feature = "templates")]
+    fn or_regex_var(
+        &mut self,
+        nv: &str,
+        prop: &str,
+        pattern: &str,
     ) -> Box<dyn MatchConditionTrait> {
-        self.or_var(nv, prop, CompOper::Less, PropType::int(value))
+        self.or_var(
+            nv,
+            prop,
+            CompOper::Regex,
+            PropType::str(pattern.to_string()),
+        )
+    }
+    fn or_is_null(&mut self, prop: &str) -> Box<dyn MatchConditionTrait> {
+        self.or(prop, CompOper::IsNull, PropType::Null)
+    }
+    fn or_is_null_var(&mut self, nv: &str, prop: &str) -> Box<dyn MatchConditionTrait> {
+        self.or_var(nv, prop, CompOper::IsNull, PropType::Null)
+    }
+    fn or_is_not_null(&mut self, prop: &str) -> Box<dyn MatchConditionTrait> {
+        self.or(prop, CompOper::IsNotNull, PropType::Null)
+    }
+    fn or_is_not_null_var(&mut self, nv: &str, prop: &str) -> Box<dyn MatchConditionTrait> {
+        self.or_var(nv, prop, CompOper::IsNotNull, PropType::Null)
     }
 
     #[cfg(feature = "templates")]
-    fn or_moreq_int(&mut self, prop: &str, value: isize) -> Box<dyn MatchConditionTrait> {
-        self.or(prop, CompOper::MoreEqual, PropType::int(value))
+    fn or_within_distance(
+        &mut self,
+        prop: &str,
+        point: Point,
+        distance: f64,
+    ) -> Box<dyn MatchConditionTrait> {
+        let nv = self.nv.clone();
+        self.or_within_distance_var(&nv, prop, point, distance)
     }
-
     #[cfg(feature = "templates")]
-    fn or_moreq_int_var(
+    fn or_within_distance_var(
         &mut self,
         nv: &str,
         prop: &str,
-        value: isize,
+        point: Point,
+        distance: f64,
     ) -> Box<dyn MatchConditionTrait> {
-        self.or_var(nv, prop, CompOper::MoreEqual, PropType::int(value))
+        self.or_expr(within_distance(nv, prop, point, distance))
     }
-
     #[cfg(feature = "templates")]
-    fn or_leseq_int(&mut self, prop: &str, value: isize) -> Box<dyn MatchConditionTrait> {
-        self.or(prop, CompOper::LessEqual, PropType::int(value))
+    fn or_within_bbox(
+        &mut self,
+        prop: &str,
+        lower_left: Point,
+        upper_right: Point,
+    ) -> Box<dyn MatchConditionTrait> {
+        let nv = self.nv.clone();
+        self.or_within_bbox_var(&nv, prop, lower_left, upper_right)
     }
-
     #[cfg(feature = "templates")]
-    fn or_leseq_int_var(
+    fn or_within_bbox_var(
         &mut self,
         nv: &str,
         prop: &str,
-        value: isize,
+        lower_left: Point,
+        upper_right: Point,
     ) -> Box<dyn MatchConditionTrait> {
-        self.or_var(nv, prop, CompOper::LessEqual, PropType::int(value))
+        self.or_expr(within_bbox(nv, prop, lower_left, upper_right))
     }
 }
 
 impl QueryTrait for MatchConditionQuery {
     fn create(&mut self, entitys: Vec<&Entity>) -> Box<dyn ReturnTrait> {
-        super::create_method(&mut self.state, entitys)
+        super::create_method(&self.state, entitys)
     }
 
     fn r#match(&mut self, entity: &Entity, optional: bool) -> Box<dyn MatchTrait> {
-        super::match_method(&mut self.state, entity, optional)
+        super::match_method(&self.state, entity, optional)
+    }
+
+    fn match_pattern(&mut self, pattern: Pattern, optional: bool) -> Box<dyn MatchTrait> {
+        super::match_pattern_method(&self.state, pattern, optional)
+    }
+
+    fn match_props(
+        &mut self,
+        entity: &Entity,
+        mode: PropsMatch,
+        fields: Option<Vec<&str>>,
+        optional: bool,
+    ) -> Box<dyn MatchConditionTrait> {
+        super::match_props_method(&self.state, entity, mode, fields, optional)
+    }
+
+    fn merge(&mut self, entity: &Entity) -> Box<dyn MergeTrait> {
+        super::merge_method(&self.state, entity)
     }
 }
 
@@ -690,6 +1710,28 @@ pub trait MatchTrait: 'static {
         op: CompOper,
         eq: PropType,
     ) -> Box<dyn MatchConditionTrait>;
+    /// **WHERE** with a list of values:
+    ///
+    /// `WHERE n.prop IN [...]`
+    fn where_in(&self, prop: &str, values: Vec<PropType>) -> Box<dyn MatchConditionTrait>;
+    /// **WHERE** with a list of values and custom var:
+    ///
+    /// `WHERE n.prop IN [...]`
+    fn where_in_var(
+        &self,
+        nv: &str,
+        prop: &str,
+        values: Vec<PropType>,
+    ) -> Box<dyn MatchConditionTrait>;
+    /// **WHERE** query function with a condition expression built from
+    /// `Expr::cond` and combined with `and`, `or`, `xor`, `!` and `group`:
+    ///
+    /// `WHERE (a.x = 1 OR a.y = 2) AND NOT a.z = 3`
+    fn where_expr(&self, expr: Expr) -> Box<dyn MatchConditionTrait>;
+    /// **WITH** query function right after the **MATCH**:
+    ///
+    /// `WITH n, n.age AS age`
+    fn with(&self, items: Vec<ReturnItem>) -> Box<dyn WithTrait>;
 
     /// A short use case for the where function, assuming the following final result:
     ///
@@ -781,32 +1823,206 @@ pub trait MatchTrait: 'static {
         prop: &str,
         value: isize,
     ) -> Box<dyn MatchConditionTrait>;
+
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `WHERE n.prop <> '...'`
+    #[cfg(feature = "templates")]
+    fn where_ne_str(&self, prop: &str, value: &str) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `WHERE n.prop <> '...'`
+    #[cfg(feature = "templates")]
+    fn where_ne_str_var(&self, nv: &str, prop: &str, value: &str) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `WHERE n.prop <> 0`
+    #[cfg(feature = "templates")]
+    fn where_ne_int(&self, prop: &str, value: isize) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `WHERE n.prop <> 0`
+    #[cfg(feature = "templates")]
+    fn where_ne_int_var(&self, nv: &str, prop: &str, value: isize) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `WHERE n.prop <> true`
+    #[cfg(feature = "templates")]
+    fn where_ne_bool(&self, prop: &str, value: bool) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `WHERE n.prop <> true`
+    #[cfg(feature = "templates")]
+    fn where_ne_bool_var(&self, nv: &str, prop: &str, value: bool) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `WHERE n.prop IN ['...', '...']`
+    #[cfg(feature = "templates")]
+    fn where_in_str(&self, prop: &str, values: Vec<&str>) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `WHERE n.prop IN ['...', '...']`
+    #[cfg(feature = "templates")]
+    fn where_in_str_var(
+        &self,
+        nv: &str,
+        prop: &str,
+        values: Vec<&str>,
+    ) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `WHERE n.prop IN [0, 1]`
+    #[cfg(feature = "templates")]
+    fn where_in_int(&self, prop: &str, values: Vec<isize>) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `WHERE n.prop IN [0, 1]`
+    #[cfg(feature = "templates")]
+    fn where_in_int_var(
+        &self,
+        nv: &str,
+        prop: &str,
+        values: Vec<isize>,
+    ) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `WHERE n.prop STARTS WITH '...'`
+    #[cfg(feature = "templates")]
+    fn where_starts_with(&self, prop: &str, value: &str) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `WHERE n.prop STARTS WITH '...'`
+    #[cfg(feature = "templates")]
+    fn where_starts_with_var(
+        &self,
+        nv: &str,
+        prop: &str,
+        value: &str,
+    ) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `WHERE n.prop ENDS WITH '...'`
+    #[cfg(feature = "templates")]
+    fn where_ends_with(&self, prop: &str, value: &str) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `WHERE n.prop ENDS WITH '...'`
+    #[cfg(feature = "templates")]
+    fn where_ends_with_var(
+        &self,
+        nv: &str,
+        prop: &str,
+        value: &str,
+    ) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `WHERE n.prop CONTAINS '...'`
+    #[cfg(feature = "templates")]
+    fn where_contains(&self, prop: &str, value: &str) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `WHERE n.prop CONTAINS '...'`
+    #[cfg(feature = "templates")]
+    fn where_contains_var(&self, nv: &str, prop: &str, value: &str)
+        -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `WHERE n.prop =~ '...'`
+    #[cfg(feature = "templates")]
+    fn where_regex(&self, prop: &str, pattern: &str) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `WHERE n.prop =~ '...'`
+    #[cfg(feature = "templates")]
+    fn where_regex_var(&self, nv: &str, prop: &str, pattern: &str) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `WHERE n.prop IS NULL`
+    fn where_is_null(&self, prop: &str) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `WHERE n.prop IS NULL`
+    fn where_is_null_var(&self, nv: &str, prop: &str) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `WHERE n.prop IS NOT NULL`
+    fn where_is_not_null(&self, prop: &str) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `WHERE n.prop IS NOT NULL`
+    fn where_is_not_null_var(&self, nv: &str, prop: &str) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `WHERE point.distance(n.prop, point({...})) <= ...`
+    #[cfg(feature = "templates")]
+    fn where_within_distance(
+        &self,
+        prop: &str,
+        point: Point,
+        distance: f64,
+    ) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `WHERE point.distance(n.prop, point({...})) <= ...`
+    #[cfg(feature = "templates")]
+    fn where_within_distance_var(
+        &self,
+        nv: &str,
+        prop: &str,
+        point: Point,
+        distance: f64,
+    ) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function, assuming the following final result:
+    ///
+    /// `WHERE point.withinBBox(n.prop, point({...}), point({...}))`
+    #[cfg(feature = "templates")]
+    fn where_within_bbox(
+        &self,
+        prop: &str,
+        lower_left: Point,
+        upper_right: Point,
+    ) -> Box<dyn MatchConditionTrait>;
+    /// A short use case for the where function with custom var, assuming the following final result:
+    ///
+    /// `WHERE point.withinBBox(n.prop, point({...}), point({...}))`
+    #[cfg(feature = "templates")]
+    fn where_within_bbox_var(
+        &self,
+        nv: &str,
+        prop: &str,
+        lower_left: Point,
+        upper_right: Point,
+    ) -> Box<dyn MatchConditionTrait>;
 }
 
 pub struct MatchQuery {
     nv: String,
-    state: String,
+    state: State,
 }
 
 impl MatchQuery {
-    pub fn new(nv: String, state: String) -> Self {
+    pub fn new(nv: String, state: State) -> Self {
         MatchQuery { nv, state }
     }
 }
 
 impl MatchTrait for MatchQuery {
     fn r#where(&self, prop: &str, op: CompOper, eq: PropType) -> Box<dyn MatchConditionTrait> {
-        let state = format!(
-            "{prev_state} WHERE {node_var}.{prop_name} {operator} {value}",
-            prev_state = self.state,
-            node_var = self.nv,
-            prop_name = prop,
-            operator = op,
-            value = eq.to_prop()
-        );
+        let mut state = self.state.clone();
+        add_condition(&mut state, Expr::cond(&self.nv, prop, op, eq), false);
+        Box::new(MatchConditionQuery::new(self.nv.clone(), state))
+    }
+
+    fn where_expr(&self, expr: Expr) -> Box<dyn MatchConditionTrait> {
+        let mut state = self.state.clone();
+        add_condition(&mut state, expr, false);
         Box::new(MatchConditionQuery::new(self.nv.clone(), state))
     }
 
+    fn with(&self, items: Vec<ReturnItem>) -> Box<dyn WithTrait> {
+        super::with_query::with_method(&self.state, items)
+    }
+
     /* Equal */
 
     #[cfg(feature = "templates")]
@@ -906,14 +2122,265 @@ impl MatchTrait for MatchQuery {
         op: CompOper,
         eq: PropType,
     ) -> Box<dyn MatchConditionTrait> {
-        let state = format!(
-            "{prev_state} WHERE {node_var}.{prop_name} {operator} {value}",
-            prev_state = self.state,
-            node_var = nv,
-            prop_name = prop,
-            operator = op,
-            value = eq.to_prop()
-        );
+        let mut state = self.state.clone();
+        add_condition(&mut state, Expr::cond(nv, prop, op, eq), false);
         Box::new(MatchConditionQuery::new(self.nv.clone(), state))
     }
+
+    fn where_in(&self, prop: &str, values: Vec<PropType>) -> Box<dyn MatchConditionTrait> {
+        self.r#where(prop, CompOper::In, PropType::Array(values))
+    }
+
+    fn where_in_var(
+        &self,
+        nv: &str,
+        prop: &str,
+        values: Vec<PropType>,
+    ) -> Box<dyn MatchConditionTrait> {
+        self.where_var(nv, prop, CompOper::In, PropType::Array(values))
+    }
+
+    /* Other operators */
+
+    #[cfg(feature = "templates")]
+    fn where_ne_str(&self, prop: &str, value: &str) -> Box<dyn MatchConditionTrait> {
+        self.r#where(prop, CompOper::NotEqual, PropType::str(value.to_string()))
+    }
+    #[cfg(feature = "templates")]
+    fn where_ne_str_var(&self, nv: &str, prop: &str, value: &str) -> Box<dyn MatchConditionTrait> {
+        self.where_var(
+            nv,
+            prop,
+            CompOper::NotEqual,
+            PropType::str(value.to_string()),
+        )
+    }
+    #[cfg(feature = "templates")]
+    fn where_ne_int(&self, prop: &str, value: isize) -> Box<dyn MatchConditionTrait> {
+        self.r#where(prop, CompOper::NotEqual, PropType::int(value))
+    }
+    #[cfg(feature = "templates")]
+    fn where_ne_int_var(&self, nv: &str, prop: &str, value: isize) -> Box<dyn MatchConditionTrait> {
+        self.where_var(nv, prop, CompOper::NotEqual, PropType::int(value))
+    }
+    #[cfg(feature = "templates")]
+    fn where_ne_bool(&self, prop: &str, value: bool) -> Box<dyn MatchConditionTrait> {
+        self.r#where(prop, CompOper::NotEqual, PropType::Bool(value))
+    }
+    #[cfg(feature = "templates")]
+    fn where_ne_bool_var(&self, nv: &str, prop: &str, value: bool) -> Box<dyn MatchConditionTrait> {
+        self.where_var(nv, prop, CompOper::NotEqual, PropType::Bool(value))
+    }
+    #[cfg(feature = "templates")]
+    fn where_in_str(&self, prop: &str, values: Vec<&str>) -> Box<dyn MatchConditionTrait> {
+        self.r#where(
+            prop,
+            CompOper::In,
+            PropType::Array(
+                values
+                    .into_iter()
+                    .map(|v| PropType::str(v.to_string()))
+                    .collect(),
+            ),
+        )
+    }
+    #[cfg(feature = "templates")]
+    fn where_in_str_var(
+        &self,
+        nv: &str,
+        prop: &str,
+        values: Vec<&str>,
+    ) -> Box<dyn MatchConditionTrait> {
+        self.where_var(
+            nv,
+            prop,
+            CompOper::In,
+            PropType::Array(
+                values
+                    .into_iter()
+                    .map(|v| PropType::str(v.to_string()))
+                    .collect(),
+            ),
+        )
+    }
+    #[cfg(feature = "templates")]
+    fn where_in_int(&self, prop: &str, values: Vec<isize>) -> Box<dyn MatchConditionTrait> {
+        self.r#where(
+            prop,
+            CompOper::In,
+            PropType::Array(values.into_iter().map(PropType::int).collect()),
+        )
+    }
+    #[cfg(feature = "templates")]
+    fn where_in_int_var(
+        &self,
+        nv: &str,
+        prop: &str,
+        values: Vec<isize>,
+    ) -> Box<dyn MatchConditionTrait> {
+        self.where_var(
+            nv,
+            prop,
+            CompOper::In,
+            PropType::Array(values.into_iter().map(PropType::int).collect()),
+        )
+    }
+    #[cfg(feature = "templates")]
+    fn where_starts_with(&self, prop: &str, value: &str) -> Box<dyn MatchConditionTrait> {
+        self.r#where(prop, CompOper::StartsWith, PropType::str(value.to_string()))
+    }
+    #[cfg(feature = "templates")]
+    fn where_starts_with_var(
+        &self,
+        nv: &str,
+        prop: &str,
+        value: &str,
+    ) -> Box<dyn MatchConditionTrait> {
+        self.where_var(
+            nv,
+            prop,
+            CompOper::StartsWith,
+            PropType::str(value.to_string()),
+        )
+    }
+    #[cfg(feature = "templates")]
+    fn where_ends_with(&self, prop: &str, value: &str) -> Box<dyn MatchConditionTrait> {
+        self.r#where(prop, CompOper::EndsWith, PropType::str(value.to_string()))
+    }
+    #[cfg(feature = "templates")]
+    fn where_ends_with_var(
+        &self,
+        nv: &str,
+        prop: &str,
+        value: &str,
+    ) -> Box<dyn MatchConditionTrait> {
+        self.where_var(
+            nv,
+            prop,
+            CompOper::EndsWith,
+            PropType::str(value.to_string()),
+        )
+    }
+    #[cfg(feature = "templates")]
+    fn where_contains(&self, prop: &str, value: &str) -> Box<dyn MatchConditionTrait> {
+        self.r#where(prop, CompOper::Contains, PropType::str(value.to_string()))
+    }
+    #[cfg(feature = "templates")]
+    fn where_contains_var(
+        &self,
+        nv: &str,
+        prop: &str,
+        value: &str,
+    ) -> Box<dyn MatchConditionTrait> {
+        self.where_var(
+            nv,
+            prop,
+            CompOper::Contains,
+            PropType::str(value.to_string()),
+        )
+    }
+    #[cfg(feature = "templates")]
+    fn where_regex(&self, prop: &str, pattern: &str) -> Box<dyn MatchConditionTrait> {
+        self.r#where(prop, CompOper::Regex, PropType::str(pattern.to_string()))
+    }
+    #[cfg(feature = "templates")]
+    fn where_regex_var(&self, nv: &str, prop: &str, pattern: &str) -> Box<dyn MatchConditionTrait> {
+        self.where_var(
+            nv,
+            prop,
+            CompOper::Regex,
+            PropType::str(pattern.to_string()),
+        )
+    }
+    fn where_is_null(&self, prop: &str) -> Box<dyn MatchConditionTrait> {
+        self.r#where(prop, CompOper::IsNull, PropType::Null)
+    }
+    fn where_is_null_var(&self, nv: &str, prop: &str) -> Box<dyn MatchConditionTrait> {
+        self.where_var(nv, prop, CompOper::IsNull, PropType::Null)
+    }
+    fn where_is_not_null(&self, prop: &str) -> Box<dyn MatchConditionTrait> {
+        self.r#where(prop, CompOper::IsNotNull, PropType::Null)
+    }
+    fn where_is_not_null_var(&self, nv: &str, prop: &str) -> Box<dyn MatchConditionTrait> {
+        self.where_var(nv, prop, CompOper::IsNotNull, PropType::Null)
+    }
+
+    #[cfg(feature = "templates")]
+    fn where_within_distance(
+        &self,
+        prop: &str,
+        point: Point,
+        distance: f64,
+    ) -> Box<dyn MatchConditionTrait> {
+        let nv = self.nv.clone();
+        self.where_within_distance_var(&nv, prop, point, distance)
+    }
+    #[cfg(feature = "templates")]
+    fn where_within_distance_var(
+        &self,
+        nv: &str,
+        prop: &str,
+        point: Point,
+        distance: f64,
+    ) -> Box<dyn MatchConditionTrait> {
+        self.where_expr(within_distance(nv, prop, point, distance))
+    }
+    #[cfg(feature = "templates")]
+    fn where_within_bbox(
+        &self,
+        prop: &str,
+        lower_left: Point,
+        upper_right: Point,
+    ) -> Box<dyn MatchConditionTrait> {
+        let nv = self.nv.clone();
+        self.where_within_bbox_var(&nv, prop, lower_left, upper_right)
+    }
+    #[cfg(feature = "templates")]
+    fn where_within_bbox_var(
+        &self,
+        nv: &str,
+        prop: &str,
+        lower_left: Point,
+        upper_right: Point,
+    ) -> Box<dyn MatchConditionTrait> {
+        self.where_expr(within_bbox(nv, prop, lower_left, upper_right))
+    }
+}
+
+/// Point property within a distance: `point.distance(nv.prop, point) <= distance`
+#[cfg(feature = "templates")]
+fn within_distance(nv: &str, prop: &str, point: Point, distance: f64) -> Expr {
+    Expr::distance(Expr::prop(nv, prop), Expr::Value(point.into()))
+        .compare(CompOper::LessEqual, PropType::float(distance))
+}
+
+/// Point property inside a box: `point.withinBBox(nv.prop, lower_left, upper_right)`
+#[cfg(feature = "templates")]
+fn within_bbox(nv: &str, prop: &str, lower_left: Point, upper_right: Point) -> Expr {
+    Expr::within_bbox(
+        Expr::prop(nv, prop),
+        Expr::Value(lower_left.into()),
+        Expr::Value(upper_right.into()),
+    )
+}
+
+/// Add a condition to the **WHERE** part of the last **MATCH** clause.
+///
+/// Conditions are joined in the order they are written, so `AND` binds
+/// tighter than a preceding `OR`, just like it reads in the query text.
+fn add_condition(state: &mut State, condition: Expr, or: bool) {
+    if let Some(Clause::Match { predicate, .. }) = state.clauses_mut().last_mut() {
+        *predicate = Some(match predicate.take() {
+            None => condition,
+            Some(prev) if or => Expr::Or(Box::new(prev), Box::new(condition)),
+            Some(prev) => and_last(prev, condition),
+        });
+    }
+}
+
+fn and_last(expr: Expr, condition: Expr) -> Expr {
+    match expr {
+        Expr::Or(left, right) => Expr::Or(left, Box::new(and_last(*right, condition))),
+        expr => Expr::And(Box::new(expr), Box::new(condition)),
+    }
 }
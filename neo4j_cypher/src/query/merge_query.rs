@@ -0,0 +1,126 @@
+use crate::entity::{Entity, Props};
+use crate::query::ast::{Clause, Expr, MergeAction, Pattern, ReturnItem, SetItem};
+use crate::query::finalize::FinalizeTrait;
+use crate::query::match_query::{MatchConditionTrait, MatchTrait};
+use crate::query::return_query::{ReturnParamTrait, ReturnTrait};
+use crate::query::state::{Params, State};
+use crate::query::with_query::WithTrait;
+use crate::query::{PropsMatch, QueryTrait};
+
+pub trait MergeTrait: 'static + ReturnTrait + QueryTrait {
+    /// **ON CREATE SET** query function.
+    ///
+    /// Properties are set only if the **MERGE** pattern had to be created:
+    ///
+    /// `ON CREATE SET n.prop = '...', n.other = 0`
+    fn on_create_set(&self, props: Props) -> Box<dyn MergeTrait>;
+
+    /// **ON MATCH SET** query function.
+    ///
+    /// Properties are set only if the **MERGE** pattern already existed:
+    ///
+    /// `ON MATCH SET n.prop = '...', n.other = 0`
+    fn on_match_set(&self, props: Props) -> Box<dyn MergeTrait>;
+}
+
+pub struct MergeQuery {
+    nv: String,
+    state: State,
+}
+
+impl MergeQuery {
+    pub fn new(nv: String, state: State) -> Self {
+        MergeQuery { nv, state }
+    }
+
+    fn on_set(&self, action: fn(Vec<SetItem>) -> MergeAction, props: Props) -> Box<dyn MergeTrait> {
+        let mut state = self.state.clone();
+        let items = super::set_items(&self.nv, &props);
+
+        if !items.is_empty() {
+            if let Some(Clause::Merge { actions, .. }) = state.clauses_mut().last_mut() {
+                actions.push(action(items));
+            }
+        }
+
+        Box::new(Self::new(self.nv.clone(), state))
+    }
+}
+
+impl MergeTrait for MergeQuery {
+    fn on_create_set(&self, props: Props) -> Box<dyn MergeTrait> {
+        self.on_set(MergeAction::OnCreate, props)
+    }
+
+    fn on_match_set(&self, props: Props) -> Box<dyn MergeTrait> {
+        self.on_set(MergeAction::OnMatch, props)
+    }
+}
+
+impl FinalizeTrait for MergeQuery {
+    fn finalize(&self) -> String {
+        self.state.query()
+    }
+
+    fn finalize_with_params(&self) -> (String, Params) {
+        self.state.render()
+    }
+
+    fn finalize_state(&self) -> State {
+        self.state.clone()
+    }
+}
+
+impl ReturnTrait for MergeQuery {
+    fn r#return(&mut self, nv: &str) -> Box<dyn ReturnParamTrait> {
+        super::return_query::return_method(&self.state, vec![nv], None)
+    }
+
+    fn return_field(&mut self, nv: &str, field: &str) -> Box<dyn ReturnParamTrait> {
+        super::return_query::return_method(&self.state, vec![nv], Some(field))
+    }
+
+    fn return_many(&mut self, nvs: Vec<&str>) -> Box<dyn ReturnParamTrait> {
+        super::return_query::return_method(&self.state, nvs, None)
+    }
+
+    fn return_items(&mut self, items: Vec<ReturnItem>) -> Box<dyn ReturnParamTrait> {
+        super::return_query::return_items_method(&self.state, items)
+    }
+
+    fn return_all(&mut self) -> Box<dyn ReturnParamTrait> {
+        super::return_query::return_items_method(&self.state, vec![Expr::all().into()])
+    }
+
+    fn with(&mut self, items: Vec<ReturnItem>) -> Box<dyn WithTrait> {
+        super::with_query::with_method(&self.state, items)
+    }
+}
+
+impl QueryTrait for MergeQuery {
+    fn create(&mut self, entitys: Vec<&Entity>) -> Box<dyn ReturnTrait> {
+        super::create_method(&self.state, entitys)
+    }
+
+    fn r#match(&mut self, entity: &Entity, optional: bool) -> Box<dyn MatchTrait> {
+        super::match_method(&self.state, entity, optional)
+    }
+
+    fn match_pattern(&mut self, pattern: Pattern, optional: bool) -> Box<dyn MatchTrait> {
+        super::match_pattern_method(&self.state, pattern, optional)
+    }
+
+    fn match_props(
+        &mut self,
+        entity: &Entity,
+        mode: PropsMatch,
+        fields: Option<Vec<&str>>,
+        optional: bool,
+    ) -> Box<dyn MatchConditionTrait> {
+        super::match_props_method(&self.state, entity, mode, fields, optional)
+    }
+
+    fn merge(&mut self, entity: &Entity) -> Box<dyn MergeTrait> {
+        super::merge_method(&self.state, entity)
+    }
+}
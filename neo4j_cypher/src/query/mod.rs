@@ -1,139 +1,520 @@
+pub mod ast;
 pub mod finalize;
 pub mod match_query;
+pub mod merge_query;
+pub mod render;
 pub mod return_query;
+pub mod state;
+pub mod with_query;
 
 use crate::entity::Entity;
+use crate::entity::Label;
+use crate::entity::NodeTrait;
 use crate::entity::PropType;
 use crate::entity::Props;
-use crate::query::match_query::{MatchQuery, MatchTrait};
+use crate::query::ast::{
+    Clause, Expr, Hop, MergeAction, NodePattern, PathPattern, Pattern, RelPattern, SetItem,
+};
+use crate::query::match_query::{
+    CompOper, MatchConditionQuery, MatchConditionTrait, MatchQuery, MatchTrait,
+};
+use crate::query::merge_query::{MergeQuery, MergeTrait};
 use crate::query::return_query::{ReturnQuery, ReturnTrait};
+use crate::query::state::State;
 
 pub trait QueryTrait: 'static {
     fn create(&mut self, entitys: Vec<&Entity>) -> Box<dyn ReturnTrait>;
 
+    /// **MATCH** query function, or **OPTIONAL MATCH** if `optional` is set.
     fn r#match(&mut self, entity: &Entity, optional: bool) -> Box<dyn MatchTrait>;
+
+    /// **MATCH** of a pattern built with `PathPattern`, or **OPTIONAL MATCH** if `optional` is set:
+    ///
+    /// `MATCH (a:Profile)-[r:KNOWS|LIKES*1..3]->(b)<-[:OWNS]-(c)`
+    ///
+    /// Templates like `where_eq_str` refer to the first node of the pattern.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the first node of the pattern has no variable.
+    fn match_pattern(&mut self, pattern: Pattern, optional: bool) -> Box<dyn MatchTrait>;
+
+    /// **MATCH** of an entity by its own properties, or **OPTIONAL MATCH** if `optional` is set:
+    ///
+    /// `MATCH (n:Profile { name: 'x', age: 3 })` or `MATCH (n:Profile) WHERE n.name = 'x' AND n.age = 3`
+    ///
+    /// `fields` restricts the match to the listed properties, in that order.
+    /// Null properties and fields the entity does not have are skipped.
+    /// The properties of a relation are compared on `r`.
+    fn match_props(
+        &mut self,
+        entity: &Entity,
+        mode: PropsMatch,
+        fields: Option<Vec<&str>>,
+        optional: bool,
+    ) -> Box<dyn MatchConditionTrait>;
+
+    /// **MERGE** query function.
+    ///
+    /// Matches the whole pattern of the entity, including all of its
+    /// properties, or creates it if it does not exist.
+    fn merge(&mut self, entity: &Entity) -> Box<dyn MergeTrait>;
+}
+
+/// How `match_props` compares the properties of an entity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropsMatch {
+    /// Properties map in the pattern: `(n:Profile { name: 'x' })`
+    Inline,
+    /// **WHERE** conjunction of equalities: `WHERE n.name = 'x' AND n.age = 3`
+    Where,
 }
 
 pub struct Query {
-    state: String,
+    state: State,
 }
 
 impl Query {
     pub fn init() -> Self {
         Query {
-            state: String::new(),
+            state: State::default(),
+        }
+    }
+
+    /// Start a query in parameter mode.
+    ///
+    /// Values are not inlined into the query text, instead they are written as
+    /// `$p0`, `$p1`, ... placeholders and returned by `finalize_with_params`.
+    pub fn init_with_params() -> Self {
+        Query {
+            state: State::with_params(String::new()),
         }
     }
 
     pub fn new(state: String) -> Self {
-        Query { state }
+        Query {
+            state: State::new(state),
+        }
+    }
+
+    /// Bulk **CREATE** of nodes with **UNWIND**:
+    ///
+    /// `UNWIND $rows AS row CREATE (n:Label) SET n = row`
+    ///
+    /// Properties of all items are passed as a single list of maps in the `$rows`
+    /// parameter, in literal mode as well, so the query text does not grow with
+    /// the number of items. Use `finalize_with_params` to get it. Labels from `#[cypher(label)]`
+    /// fields are applied to every row separately. An empty slice adds nothing.
+    pub fn create_unwind<T: NodeTrait>(&mut self, nv: &str, items: &[T]) -> Box<dyn ReturnTrait> {
+        unwind_method(&self.state, nv, items, false)
+    }
+
+    /// Bulk **MERGE** of nodes with **UNWIND**, keyed on `#[cypher(key)]` fields:
+    ///
+    /// `UNWIND $rows AS row MERGE (n:Label { key: row.key }) SET n += row`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` has no key fields.
+    pub fn merge_unwind<T: NodeTrait>(&mut self, nv: &str, items: &[T]) -> Box<dyn ReturnTrait> {
+        unwind_method(&self.state, nv, items, true)
     }
 }
 
 impl QueryTrait for Query {
     fn create(&mut self, entitys: Vec<&Entity>) -> Box<dyn ReturnTrait> {
-        create_method(&mut self.state, entitys)
+        create_method(&self.state, entitys)
     }
 
     fn r#match(&mut self, entity: &Entity, optional: bool) -> Box<dyn MatchTrait> {
-        match_method(&mut self.state, entity, optional)
+        match_method(&self.state, entity, optional)
     }
+
+    fn match_pattern(&mut self, pattern: Pattern, optional: bool) -> Box<dyn MatchTrait> {
+        match_pattern_method(&self.state, pattern, optional)
+    }
+
+    fn match_props(
+        &mut self,
+        entity: &Entity,
+        mode: PropsMatch,
+        fields: Option<Vec<&str>>,
+        optional: bool,
+    ) -> Box<dyn MatchConditionTrait> {
+        match_props_method(&self.state, entity, mode, fields, optional)
+    }
+
+    fn merge(&mut self, entity: &Entity) -> Box<dyn MergeTrait> {
+        merge_method(&self.state, entity)
+    }
+}
+
+pub(super) fn match_method(state: &State, entity: &Entity, optional: bool) -> Box<dyn MatchTrait> {
+    let mut state = state.clone();
+    let (nv, pattern) = match_entity_pattern(entity);
+
+    state.push(Clause::Match {
+        optional,
+        pattern,
+        predicate: None,
+    });
+
+    Box::new(MatchQuery::new(nv, state))
 }
 
-pub(super) fn match_method(
-    state: &mut str,
+pub(super) fn match_props_method(
+    state: &State,
     entity: &Entity,
+    mode: PropsMatch,
+    fields: Option<Vec<&str>>,
     optional: bool,
-) -> Box<dyn MatchTrait> {
+) -> Box<dyn MatchConditionTrait> {
+    let mut state = state.clone();
+    let (nv, mut pattern) = match_entity_pattern(entity);
+
+    let (Entity::Node { props, .. } | Entity::Relation { props, .. }) = entity;
+    let props = props.clone().unwrap_or_default();
+
+    // Listed fields keep their order, otherwise all of the properties are used.
+    let selected = match fields {
+        Some(fields) => fields
+            .iter()
+            .filter_map(|field| props.get(field).map(|v| (field.to_string(), v.clone())))
+            .collect::<Vec<_>>(),
+        None => props.into_iter().collect(),
+    };
+    let selected = selected
+        .into_iter()
+        .filter(|(_, v)| *v != PropType::Null)
+        .collect::<Vec<_>>();
+
+    let predicate = match mode {
+        PropsMatch::Inline => {
+            let props = selected
+                .into_iter()
+                .map(|(k, v)| (k, Expr::Value(v)))
+                .collect();
+            match &mut pattern {
+                Pattern::Node(node) => node.props = props,
+                Pattern::Path(path) => {
+                    if let Some(Hop::Rel(rel, _)) = path.hops.first_mut() {
+                        rel.props = props;
+                    }
+                }
+            }
+
+            None
+        }
+        PropsMatch::Where => selected
+            .into_iter()
+            .map(|(k, v)| Expr::cond(&nv, &k, CompOper::Equal, v))
+            .reduce(Expr::and),
+    };
+
+    state.push(Clause::Match {
+        optional,
+        pattern,
+        predicate,
+    });
+
+    Box::new(MatchConditionQuery::new(nv, state))
+}
+
+/// Pattern used to match an entity, with the variable that names it.
+///
+/// Nodes keep all of their labels, keyed nodes are matched by their key properties.
+/// Relationships are named `r`.
+fn match_entity_pattern(entity: &Entity) -> (String, Pattern) {
     match entity {
-        Entity::Node { nv, node_name, .. } => {
-            let new_state = format!(
-                "MATCH ({node_var}:{node_name})",
-                node_var = nv,
-                node_name = node_name
-            );
+        Entity::Node {
+            nv,
+            node_name,
+            props,
+            labels,
+            keys,
+        } => {
+            let all_labels = node_labels(node_name, labels);
 
-            let state = format!(
-                "{state}{indent}{new_state}",
-                state = state,
-                indent = if state.len() > 0 { "\n" } else { "" },
-                new_state = new_state
-            );
+            let props = match (props, keys) {
+                (Some(props), Some(keys)) => pattern_props(&split_keys(props, keys).0),
+                _ => Vec::new(),
+            };
 
-            Box::new(MatchQuery::new(nv.to_string(), state.to_string()))
+            let pattern = Pattern::Node(NodePattern {
+                var: Some(nv.to_string()),
+                labels: all_labels,
+                label_expr: None,
+                props,
+            });
+
+            (nv.to_string(), pattern)
         }
 
         Entity::Relation { from, to, name, .. } => {
-            let new_state = format!(
-                "{opt}MATCH ({from_nv}{from_name})-[r:{rel_name}]->({to_nv}{to_name})",
-                opt = if optional {
-                    format!("OPTIONAL ")
-                } else {
-                    String::new()
+            let pattern = relation_pattern(
+                NodePattern {
+                    props: Vec::new(),
+                    ..from.into()
+                },
+                RelPattern::outgoing().var("r").r#type(name),
+                &None,
+                NodePattern {
+                    props: Vec::new(),
+                    ..to.into()
                 },
-                from_nv = from.nv(),
-                from_name = format!(":{}", from.node_name()),
-                rel_name = name,
-                to_nv = to.nv(),
-                to_name = format!(":{}", to.node_name()),
             );
 
-            let state = format!(
-                "{state}{indent}{new_state}",
-                state = state,
-                indent = if state.len() > 0 { "\n" } else { "" },
-                new_state = new_state
-            );
+            (String::from("r"), pattern)
+        }
+    }
+}
+
+pub(super) fn match_pattern_method(
+    state: &State,
+    pattern: Pattern,
+    optional: bool,
+) -> Box<dyn MatchTrait> {
+    let mut state = state.clone();
+    let nv = match &pattern {
+        Pattern::Node(node) => node.var.clone(),
+        Pattern::Path(path) => path.start.var.clone(),
+    }
+    .expect("the first node of a MATCH pattern must have a variable");
+
+    state.push(Clause::Match {
+        optional,
+        pattern,
+        predicate: None,
+    });
+
+    Box::new(MatchQuery::new(nv, state))
+}
+
+pub(super) fn merge_method(state: &State, entity: &Entity) -> Box<dyn MergeTrait> {
+    let mut state = state.clone();
+
+    match entity {
+        Entity::Node {
+            nv,
+            node_name,
+            props,
+            labels,
+            keys,
+        } => {
+            let all_labels = node_labels(node_name, labels);
 
-            Box::new(MatchQuery::new(String::new(), state.to_string()))
+            // Keyed nodes are merged by their key properties, the rest
+            // of them are set depending on whether the node was found.
+            let (props, actions) = match (props, keys) {
+                (Some(props), Some(keys)) => {
+                    let (key_props, mut rest) = split_keys(props, keys);
+                    rest.retain(|_, v| *v != PropType::Null);
+
+                    let actions = if rest.is_empty() {
+                        Vec::new()
+                    } else {
+                        vec![
+                            MergeAction::OnCreate(set_items(nv, &rest)),
+                            MergeAction::OnMatch(set_items(nv, &rest)),
+                        ]
+                    };
+
+                    (pattern_props(&key_props), actions)
+                }
+                (Some(props), None) => (pattern_props(props), Vec::new()),
+                (None, _) => (Vec::new(), Vec::new()),
+            };
+
+            state.push(Clause::Merge {
+                pattern: Pattern::Node(NodePattern {
+                    var: Some(nv.to_string()),
+                    labels: all_labels,
+                    label_expr: None,
+                    props,
+                }),
+                actions,
+            });
+
+            Box::new(MergeQuery::new(nv.to_string(), state))
+        }
+
+        Entity::Relation {
+            from,
+            to,
+            name,
+            props,
+        } => {
+            state.push(Clause::Merge {
+                pattern: relation_pattern(
+                    NodePattern::var(from.nv()),
+                    RelPattern::outgoing().var("r").r#type(name),
+                    props,
+                    NodePattern::var(to.nv()),
+                ),
+                actions: Vec::new(),
+            });
+
+            Box::new(MergeQuery::new(String::from("r"), state))
         }
     }
 }
 
-pub(super) fn create_method(state: &mut str, entitys: Vec<&Entity>) -> Box<dyn ReturnTrait> {
-    let new_state = entitys
+fn unwind_method<T: NodeTrait>(
+    state: &State,
+    nv: &str,
+    items: &[T],
+    merge: bool,
+) -> Box<dyn ReturnTrait> {
+    let mut state = state.clone();
+    let nodes = items.iter().map(|item| item.node(nv)).collect::<Vec<_>>();
+    let node_name = match nodes.first() {
+        Some(node) => node.node_name(),
+        None => return Box::new(ReturnQuery::new(state)),
+    };
+
+    let labels = nodes
+        .iter()
+        .map(|node| {
+            node.labels()
+                .iter()
+                .flatten()
+                .map(|label| label.to_string())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    // Labels shared by all rows are written into the pattern,
+    // the rest of them are set only for the rows that have them.
+    let common = labels[0]
+        .iter()
+        .filter(|label| labels.iter().all(|row| row.contains(label)))
+        .cloned()
+        .collect::<Vec<_>>();
+    let mut varying = Vec::new();
+    for label in labels.iter().flatten() {
+        if !common.contains(label) && !varying.contains(label) {
+            varying.push(label.clone());
+        }
+    }
+
+    let rows = nodes
         .iter()
-        .enumerate()
-        .map(|(i, entity)| match entity {
+        .zip(&labels)
+        .map(|(node, labels)| {
+            let mut props = node.props().clone().unwrap_or_default();
+            props.retain(|_, v| *v != PropType::Null);
+
+            if varying.is_empty() {
+                PropType::Map(props)
+            } else {
+                let mut row = Props::new();
+                row.insert(String::from("props"), PropType::Map(props));
+                row.insert(
+                    String::from("labels"),
+                    PropType::Array(labels.iter().map(|l| PropType::str(l.clone())).collect()),
+                );
+
+                PropType::Map(row)
+            }
+        })
+        .collect::<Vec<_>>();
+    let row = if varying.is_empty() {
+        Expr::var("row")
+    } else {
+        Expr::prop("row", "props")
+    };
+
+    let mut pattern = NodePattern {
+        var: Some(nv.to_string()),
+        labels: vec![node_name.to_string()],
+        label_expr: None,
+        props: Vec::new(),
+    };
+    pattern.labels.extend(common);
+
+    state.push(Clause::Unwind {
+        list: Expr::Param(String::from("rows"), PropType::Array(rows)),
+        var: String::from("row"),
+    });
+
+    if merge {
+        let keys = items[0].keys();
+        if keys.is_empty() {
+            panic!(
+                "bulk MERGE of `{}` nodes requires #[cypher(key)] fields",
+                node_name
+            );
+        }
+
+        pattern.props = keys
+            .iter()
+            .map(|key| {
+                (
+                    key.to_string(),
+                    Expr::Property(Box::new(row.clone()), key.to_string()),
+                )
+            })
+            .collect();
+
+        state.push(Clause::Merge {
+            pattern: Pattern::Node(pattern),
+            actions: Vec::new(),
+        });
+        state.push(Clause::Set(vec![SetItem::Extend {
+            var: nv.to_string(),
+            value: row,
+        }]));
+    } else {
+        state.push(Clause::Create(vec![Pattern::Node(pattern)]));
+        state.push(Clause::Set(vec![SetItem::Replace {
+            var: nv.to_string(),
+            value: row,
+        }]));
+    }
+
+    for label in varying {
+        state.push(Clause::Foreach {
+            var: String::from("i"),
+            list: Expr::Case {
+                when: Box::new(Expr::Compare(
+                    Box::new(Expr::Literal(PropType::str(label.clone()))),
+                    CompOper::In,
+                    Box::new(Expr::prop("row", "labels")),
+                )),
+                then: Box::new(Expr::Literal(PropType::Array(vec![PropType::int(1)]))),
+                otherwise: Box::new(Expr::Literal(PropType::Array(Vec::new()))),
+            },
+            body: vec![Clause::Set(vec![SetItem::Labels {
+                var: nv.to_string(),
+                labels: vec![label],
+            }])],
+        });
+    }
+
+    Box::new(ReturnQuery::new(state))
+}
+
+pub(super) fn create_method(state: &State, entitys: Vec<&Entity>) -> Box<dyn ReturnTrait> {
+    let mut state = state.clone();
+    // All entities are created by a single clause, as a comma-separated pattern list.
+    let patterns = entitys
+        .into_iter()
+        .map(|entity| match entity {
             Entity::Node {
                 nv,
                 node_name,
                 props,
                 labels,
+                ..
             } => {
-                let labels = if let Some(labels) = labels {
-                    labels
-                        .iter()
-                        .map(|label| {
-                            format!(
-                                "\nSET {node_var}:{label_name}",
-                                node_var = nv,
-                                label_name = label.to_string()
-                            )
-                        })
-                        .collect::<String>()
-                } else {
-                    String::new()
-                };
-
-                if let Some(props) = props {
-                    format!(
-                        "{indent}CREATE ({node_var}:{node_name} {{ {props_obj} }}){labels}",
-                        indent = if i != 0 { "\n" } else { "" },
-                        node_var = nv,
-                        node_name = node_name,
-                        props_obj = props_to_string(props),
-                        labels = labels,
-                    )
-                } else {
-                    format!(
-                        "{indent}CREATE ({node_var}:{node_name}){labels}",
-                        indent = if i != 0 { "\n" } else { "" },
-                        node_var = nv,
-                        node_name = node_name,
-                        labels = labels,
-                    )
-                }
+                let all_labels = node_labels(node_name, labels);
+
+                Pattern::Node(NodePattern {
+                    var: Some(nv.to_string()),
+                    labels: all_labels,
+                    label_expr: None,
+                    props: props.as_ref().map(pattern_props).unwrap_or_default(),
+                })
             }
 
             Entity::Relation {
@@ -141,47 +522,67 @@ pub(super) fn create_method(state: &mut str, entitys: Vec<&Entity>) -> Box<dyn R
                 to,
                 name,
                 props,
-            } => {
-                if let Some(props) = props {
-                    format!(
-                        "{start_q}({from_nv})-[:{rel_name} {{ {props_obj} }}]->({to_nv}){is_next}",
-                        start_q = if i == 0 { "CREATE " } else { "" },
-                        from_nv = from.nv(),
-                        rel_name = name,
-                        props_obj = props_to_string(props),
-                        to_nv = to.nv(),
-                        is_next = if i == entitys.len() { "," } else { "" }
-                    )
-                } else {
-                    format!(
-                        "{start_q}({from_nv})-[:{rel_name}]->({to_nv}){is_next}",
-                        start_q = if i == 0 { "CREATE " } else { "" },
-                        from_nv = from.nv(),
-                        rel_name = name,
-                        to_nv = to.nv(),
-                        is_next = if i < entitys.len() - 1 { ",\n\t" } else { "" }
-                    )
-                }
-            }
+            } => relation_pattern(
+                NodePattern::var(from.nv()),
+                RelPattern::outgoing().r#type(name),
+                props,
+                NodePattern::var(to.nv()),
+            ),
         })
-        .collect::<String>();
+        .collect::<Vec<_>>();
+
+    if !patterns.is_empty() {
+        state.push(Clause::Create(patterns));
+    }
 
-    let state = format!(
-        "{state}{indent}{new_state}",
-        state = state,
-        indent = if state.len() > 0 { "\n" } else { "" },
-        new_state = new_state
-    );
-    Box::new(ReturnQuery::new(state.to_string()))
+    Box::new(ReturnQuery::new(state))
 }
 
-fn props_to_string(props: &Props) -> String {
-    let mut props: String = props
-        .into_iter()
+/// Pattern of a single relationship: `(a)-[r:TYPE { props }]->(b)`
+fn relation_pattern(
+    from: NodePattern,
+    mut rel: RelPattern,
+    props: &Option<Props>,
+    to: NodePattern,
+) -> Pattern {
+    rel.props = props.as_ref().map(pattern_props).unwrap_or_default();
+    PathPattern::new(from).hop(rel, to).into()
+}
+
+/// Labels of a node, starting with its name.
+fn node_labels(node_name: &str, labels: &Option<Vec<Label>>) -> Vec<String> {
+    let mut all_labels = vec![node_name.to_string()];
+    all_labels.extend(labels.iter().flatten().map(|label| label.to_string()));
+    all_labels
+}
+
+/// Properties of a pattern, without the ones that are null.
+fn pattern_props(props: &Props) -> Vec<(String, Expr)> {
+    props
+        .iter()
         .filter(|(_, v)| **v != PropType::Null)
-        .map(|(k, v)| format!("{}: {},", k, v.to_prop()))
-        .collect();
-    props.pop();
+        .map(|(k, v)| (k.clone(), Expr::Value(v.clone())))
+        .collect()
+}
 
+/// Properties as a list of assignments to the properties of a variable.
+///
+/// `n.prop = '...', n.other = 0`
+pub(super) fn set_items(nv: &str, props: &Props) -> Vec<SetItem> {
     props
+        .iter()
+        .map(|(k, v)| SetItem::Property {
+            var: nv.to_string(),
+            key: k.clone(),
+            value: Expr::Value(v.clone()),
+        })
+        .collect()
+}
+
+/// Split properties into the ones listed in `keys` and the rest of them.
+fn split_keys(props: &Props, keys: &[&str]) -> (Props, Props) {
+    props
+        .iter()
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .partition(|(k, _)| keys.contains(&k.as_str()))
 }
@@ -0,0 +1,532 @@
+use crate::entity::PropType;
+use crate::ident::ident;
+use crate::query::ast::{
+    Clause, Direction, Expr, Hop, LabelExpr, Length, MergeAction, NodePattern, PathPattern,
+    Pattern, Quantifier, RelPattern, ReturnItem, SetItem, Shortest,
+};
+use crate::query::return_query::Order;
+use crate::query::state::Params;
+
+/// Turn a list of clauses into the query text.
+///
+/// In parameter mode values are collected into the returned map and
+/// written as `$p0`, `$p1`, ... placeholders in the order they appear in the text.
+/// Named parameters are returned in both modes.
+pub fn render(clauses: &[Clause], with_params: bool) -> (String, Params) {
+    let mut renderer = Renderer {
+        params: if with_params {
+            Some(Params::new())
+        } else {
+            None
+        },
+        named: Params::new(),
+    };
+
+    let query = clauses
+        .iter()
+        .map(|clause| renderer.clause(clause))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut params = renderer.params.unwrap_or_default();
+    params.extend(renderer.named);
+
+    (query, params)
+}
+
+struct Renderer {
+    params: Option<Params>,
+    named: Params,
+}
+
+impl Renderer {
+    fn clause(&mut self, clause: &Clause) -> String {
+        match clause {
+            Clause::Raw(text) => text.clone(),
+
+            Clause::Match {
+                optional,
+                pattern,
+                predicate,
+            } => {
+                let pattern = self.pattern(pattern);
+                let predicate = match predicate {
+                    Some(predicate) => format!(" WHERE {}", self.expr(predicate)),
+                    None => String::new(),
+                };
+
+                format!(
+                    "{opt}MATCH {pattern}{predicate}",
+                    opt = if *optional { "OPTIONAL " } else { "" },
+                    pattern = pattern,
+                    predicate = predicate
+                )
+            }
+
+            Clause::Create(patterns) => {
+                let patterns = patterns
+                    .iter()
+                    .map(|pattern| self.pattern(pattern))
+                    .collect::<Vec<_>>();
+
+                format!("CREATE {}", patterns.join(",\n\t"))
+            }
+
+            Clause::Merge { pattern, actions } => {
+                let mut merge = format!("MERGE {}", self.pattern(pattern));
+                for action in actions {
+                    let (name, items) = match action {
+                        MergeAction::OnCreate(items) => ("CREATE", items),
+                        MergeAction::OnMatch(items) => ("MATCH", items),
+                    };
+                    merge.push_str(&format!("\nON {} SET {}", name, self.set_items(items)));
+                }
+
+                merge
+            }
+
+            Clause::Set(items) => format!("SET {}", self.set_items(items)),
+
+            Clause::Delete { detach, vars } => format!(
+                "{detach}DELETE {vars}",
+                detach = if *detach { "DETACH " } else { "" },
+                vars = vars
+                    .iter()
+                    .map(|var| ident(var).to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+
+            Clause::Unwind { list, var } => {
+                format!("UNWIND {} AS {}", self.expr(list), ident(var))
+            }
+
+            Clause::Foreach { var, list, body } => {
+                let list = self.expr(list);
+                let body = body
+                    .iter()
+                    .map(|clause| self.clause(clause))
+                    .collect::<Vec<_>>();
+
+                format!("FOREACH ({} IN {} | {})", ident(var), list, body.join(" "))
+            }
+
+            Clause::With(projection) => {
+                let mut with = format!(
+                    "WITH {distinct}{items}",
+                    distinct = if projection.distinct { "DISTINCT " } else { "" },
+                    items = self.return_items(&projection.items)
+                );
+                if !projection.order.is_empty() {
+                    with.push_str(&format!(" {}", self.order_by(&projection.order)));
+                }
+                if let Some(skip) = projection.skip {
+                    with.push_str(&format!(" SKIP {}", skip));
+                }
+                if let Some(limit) = projection.limit {
+                    with.push_str(&format!(" LIMIT {}", limit));
+                }
+                if let Some(predicate) = &projection.predicate {
+                    with.push_str(&format!(" WHERE {}", self.expr(predicate)));
+                }
+
+                with
+            }
+
+            Clause::Return { distinct, items } => format!(
+                "RETURN {distinct}{items}",
+                distinct = if *distinct { "DISTINCT " } else { "" },
+                items = self.return_items(items)
+            ),
+
+            Clause::OrderBy(items) => self.order_by(items),
+
+            Clause::Skip(value) => format!("SKIP {}", value),
+            Clause::Limit(value) => format!("LIMIT {}", value),
+        }
+    }
+
+    fn pattern(&mut self, pattern: &Pattern) -> String {
+        match pattern {
+            Pattern::Node(node) => self.node(node),
+            Pattern::Path(path) => {
+                let text = self.path(path);
+                let text = match path.shortest {
+                    Some(Shortest::One) => format!("shortestPath({})", text),
+                    Some(Shortest::All) => format!("allShortestPaths({})", text),
+                    None => text,
+                };
+
+                match &path.name {
+                    Some(name) => format!("{} = {}", ident(name), text),
+                    None => text,
+                }
+            }
+        }
+    }
+
+    /// The chain of nodes and relationships of a path.
+    fn path(&mut self, path: &PathPattern) -> String {
+        let mut text = self.node(&path.start);
+        for hop in &path.hops {
+            match hop {
+                Hop::Rel(rel, node) => {
+                    text.push_str(&self.rel(rel));
+                    text.push_str(&self.node(node));
+                }
+                Hop::Quantified(group, node) => {
+                    let inner = self.path(&group.path);
+                    let predicate = match &group.predicate {
+                        Some(predicate) => format!(" WHERE {}", self.expr(predicate)),
+                        None => String::new(),
+                    };
+                    let quantifier = match group.quantifier {
+                        Quantifier::Plus => String::from("+"),
+                        // `{,}` is not valid Cypher, a range without bounds is `*`.
+                        Quantifier::Star
+                        | Quantifier::Range {
+                            min: None,
+                            max: None,
+                        } => String::from("*"),
+                        Quantifier::Range { min, max } if min.is_some() && min == max => {
+                            format!("{{{}}}", min.unwrap_or_default())
+                        }
+                        Quantifier::Range { min, max } => format!(
+                            "{{{},{}}}",
+                            min.map(|min| min.to_string()).unwrap_or_default(),
+                            max.map(|max| max.to_string()).unwrap_or_default()
+                        ),
+                    };
+
+                    text.push_str(&format!(
+                        " ({}{}){} {}",
+                        inner,
+                        predicate,
+                        quantifier,
+                        self.node(node)
+                    ));
+                }
+            }
+        }
+
+        text
+    }
+
+    fn node(&mut self, node: &NodePattern) -> String {
+        format!(
+            "({var}{labels}{props})",
+            var = node
+                .var
+                .as_ref()
+                .map(ident)
+                .map(|v| v.to_string())
+                .unwrap_or_default(),
+            labels = node_labels(node),
+            props = self.props(&node.props)
+        )
+    }
+
+    fn rel(&mut self, rel: &RelPattern) -> String {
+        let types = rel
+            .types
+            .iter()
+            .map(|rel_type| ident(rel_type).to_string())
+            .collect::<Vec<_>>();
+        let length = match rel.length {
+            None => String::new(),
+            Some(Length { min, max }) if min.is_some() && min == max => {
+                format!("*{}", min.unwrap_or_default())
+            }
+            Some(Length { min, max: None }) => {
+                format!(
+                    "*{}",
+                    min.map(|min| format!("{}..", min)).unwrap_or_default()
+                )
+            }
+            Some(Length {
+                min,
+                max: Some(max),
+            }) => {
+                format!(
+                    "*{}..{}",
+                    min.map(|min| min.to_string()).unwrap_or_default(),
+                    max
+                )
+            }
+        };
+
+        let (left, right) = match rel.direction {
+            Direction::Outgoing => ("-", "->"),
+            Direction::Incoming => ("<-", "-"),
+            Direction::Undirected => ("-", "-"),
+        };
+
+        format!(
+            "{left}[{var}{colon}{types}{length}{props}]{right}",
+            left = left,
+            var = rel
+                .var
+                .as_ref()
+                .map(ident)
+                .map(|v| v.to_string())
+                .unwrap_or_default(),
+            colon = if types.is_empty() { "" } else { ":" },
+            types = types.join("|"),
+            length = length,
+            props = self.props(&rel.props),
+            right = right
+        )
+    }
+
+    /// Inline property map of a pattern: ` { key: value,other: value }`
+    fn props(&mut self, props: &[(String, Expr)]) -> String {
+        if props.is_empty() {
+            return String::new();
+        }
+
+        let props = props
+            .iter()
+            .map(|(k, v)| format!("{}: {}", ident(k), self.expr(v)))
+            .collect::<Vec<_>>();
+
+        format!(" {{ {} }}", props.join(","))
+    }
+
+    fn set_items(&mut self, items: &[SetItem]) -> String {
+        items
+            .iter()
+            .map(|item| match item {
+                SetItem::Property { var, key, value } => {
+                    format!("{}.{} = {}", ident(var), ident(key), self.expr(value))
+                }
+                SetItem::Labels { var, labels } => format!(
+                    "{}{}",
+                    ident(var),
+                    labels
+                        .iter()
+                        .map(|label| format!(":{}", ident(label)))
+                        .collect::<String>()
+                ),
+                SetItem::Replace { var, value } => {
+                    format!("{} = {}", ident(var), self.expr(value))
+                }
+                SetItem::Extend { var, value } => {
+                    format!("{} += {}", ident(var), self.expr(value))
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn return_items(&mut self, items: &[ReturnItem]) -> String {
+        items
+            .iter()
+            .map(|item| self.return_item(item))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    fn order_by(&mut self, items: &[(Expr, Order)]) -> String {
+        let items = items
+            .iter()
+            .map(|(expr, order)| format!("{} {}", self.expr(expr), order))
+            .collect::<Vec<_>>();
+
+        format!("ORDER BY {}", items.join(", "))
+    }
+
+    fn return_item(&mut self, item: &ReturnItem) -> String {
+        match &item.alias {
+            Some(alias) => format!("{} AS {}", self.expr(&item.expr), ident(alias)),
+            None => self.expr(&item.expr),
+        }
+    }
+
+    fn expr(&mut self, expr: &Expr) -> String {
+        match expr {
+            Expr::Value(value) => self.value(value),
+            Expr::Literal(value) => value.to_prop(),
+            Expr::Param(name, value) => self.param(name, value),
+            Expr::Var(var) => ident(var).to_string(),
+            Expr::Property(expr, key) => {
+                format!("{}.{}", self.operand(expr, ATOM), ident(key))
+            }
+            Expr::Compare(left, op, _) if op.is_unary() => {
+                format!("{} {}", self.operand(left, ATOM), op)
+            }
+            Expr::Compare(left, op, right) => format!(
+                "{} {} {}",
+                self.operand(left, ATOM),
+                op,
+                self.operand(right, ATOM)
+            ),
+            Expr::Not(operand) => format!("NOT {}", self.operand(operand, precedence(expr))),
+            Expr::And(left, right) => self.binary(expr, "AND", left, right),
+            Expr::Xor(left, right) => self.binary(expr, "XOR", left, right),
+            Expr::Or(left, right) => self.binary(expr, "OR", left, right),
+            Expr::Group(expr) => format!("({})", self.expr(expr)),
+            Expr::All => String::from("*"),
+            Expr::HasLabels(expr, labels) => {
+                format!("{}:{}", self.operand(expr, ATOM), label_expr(labels, 0))
+            }
+            Expr::Function { name, args } => {
+                let args = args.iter().map(|arg| self.expr(arg)).collect::<Vec<_>>();
+
+                format!("{}({})", name, args.join(", "))
+            }
+            Expr::CountAll => String::from("count(*)"),
+            Expr::Aggregate {
+                func,
+                distinct,
+                args,
+            } => {
+                let args = args.iter().map(|arg| self.expr(arg)).collect::<Vec<_>>();
+
+                format!(
+                    "{func}({distinct}{args})",
+                    func = func,
+                    distinct = if *distinct { "DISTINCT " } else { "" },
+                    args = args.join(", ")
+                )
+            }
+            Expr::Case {
+                when,
+                then,
+                otherwise,
+            } => format!(
+                "CASE WHEN {} THEN {} ELSE {} END",
+                self.expr(when),
+                self.expr(then),
+                self.expr(otherwise)
+            ),
+        }
+    }
+
+    /// `left op right` for a boolean operator.
+    fn binary(&mut self, expr: &Expr, op: &str, left: &Expr, right: &Expr) -> String {
+        format!(
+            "{} {} {}",
+            self.operand(left, precedence(expr)),
+            op,
+            self.operand(right, precedence(expr))
+        )
+    }
+
+    /// Render an operand of an operator, wrapping it in parentheses
+    /// if it binds weaker than the operator itself.
+    fn operand(&mut self, expr: &Expr, min_precedence: u8) -> String {
+        if precedence(expr) < min_precedence {
+            format!("({})", self.expr(expr))
+        } else {
+            self.expr(expr)
+        }
+    }
+
+    fn value(&mut self, value: &PropType) -> String {
+        match self.params.as_ref().map(Params::len) {
+            Some(len) => {
+                // Positional names skip the ones already taken by named parameters.
+                let mut n = len;
+                while self.is_taken(&format!("p{}", n)) {
+                    n += 1;
+                }
+
+                let name = format!("p{}", n);
+                self.params
+                    .get_or_insert_with(Params::new)
+                    .insert(name.clone(), value.clone());
+
+                format!("${}", name)
+            }
+            None => value.to_prop(),
+        }
+    }
+
+    /// A named parameter, numbered when its name is already taken
+    /// by another named or positional parameter.
+    fn param(&mut self, name: &str, value: &PropType) -> String {
+        let mut unique = name.to_string();
+        let mut n = 1;
+        while self.is_taken(&unique) {
+            unique = format!("{}{}", name, n);
+            n += 1;
+        }
+        self.named.insert(unique.clone(), value.clone());
+
+        format!("${}", unique)
+    }
+
+    fn is_taken(&self, name: &str) -> bool {
+        self.named.contains_key(name)
+            || self
+                .params
+                .as_ref()
+                .is_some_and(|params| params.contains_key(name))
+    }
+}
+
+/// Precedence of expressions that never need parentheses.
+const ATOM: u8 = 5;
+
+/// How tightly an expression binds, the loosest is `OR`.
+fn precedence(expr: &Expr) -> u8 {
+    match expr {
+        Expr::Or(..) => 0,
+        Expr::Xor(..) => 1,
+        Expr::And(..) => 2,
+        Expr::Not(..) => 3,
+        Expr::Compare(..) | Expr::HasLabels(..) => 4,
+        _ => ATOM,
+    }
+}
+
+/// Labels of a node pattern: `:A:B`, or `:A&(B|C)` with a label expression.
+fn node_labels(node: &NodePattern) -> String {
+    match &node.label_expr {
+        None => node
+            .labels
+            .iter()
+            .map(|label| format!(":{}", ident(label)))
+            .collect(),
+        Some(expr) => {
+            let expr = node.labels.iter().rev().fold(expr.clone(), |expr, label| {
+                LabelExpr::label(label).and(expr)
+            });
+
+            format!(":{}", label_expr(&expr, 0))
+        }
+    }
+}
+
+/// Render a label expression, wrapping it in parentheses
+/// if it binds weaker than `min_precedence`.
+fn label_expr(expr: &LabelExpr, min_precedence: u8) -> String {
+    let precedence = match expr {
+        LabelExpr::Or(..) => 0,
+        LabelExpr::And(..) => 1,
+        LabelExpr::Not(..) => 2,
+        LabelExpr::Label(_) | LabelExpr::Any => 3,
+    };
+
+    let text = match expr {
+        LabelExpr::Label(label) => ident(label).to_string(),
+        LabelExpr::Any => String::from("%"),
+        LabelExpr::Not(expr) => format!("!{}", label_expr(expr, precedence)),
+        LabelExpr::And(left, right) => format!(
+            "{}&{}",
+            label_expr(left, precedence),
+            label_expr(right, precedence)
+        ),
+        LabelExpr::Or(left, right) => format!(
+            "{}|{}",
+            label_expr(left, precedence),
+            label_expr(right, precedence)
+        ),
+    };
+
+    if precedence < min_precedence {
+        format!("({})", text)
+    } else {
+        text
+    }
+}
@@ -1,111 +1,213 @@
+use crate::query::ast::{Clause, Expr, ReturnItem};
 use crate::query::finalize::{Finalize, FinalizeTrait};
+use crate::query::state::{Params, State};
+use crate::query::with_query::{with_method, WithTrait};
 
-pub trait SkipControlTrait: 'static + FinalizeTrait {
-    fn skip(&self, value: usize) -> Box<dyn FinalizeTrait>;
+pub trait LimitControlTrait: 'static + FinalizeTrait {
+    fn limit(&self, value: usize) -> Box<dyn FinalizeTrait>;
 }
 
-pub struct SkipControlQuery(String);
+pub struct LimitControlQuery(State);
 
-impl SkipControlTrait for SkipControlQuery {
-    fn skip(&self, value: usize) -> Box<dyn FinalizeTrait> {
-        let state = format!(
-            "{prev_state}\nSKIP {value}",
-            prev_state = self.0,
-            value = value
-        );
+impl LimitControlTrait for LimitControlQuery {
+    fn limit(&self, value: usize) -> Box<dyn FinalizeTrait> {
+        let mut state = self.0.clone();
+        state.push(Clause::Limit(value));
         Box::new(Finalize(state))
     }
 }
 
+impl FinalizeTrait for LimitControlQuery {
+    fn finalize(&self) -> String {
+        self.0.query()
+    }
+
+    fn finalize_with_params(&self) -> (String, Params) {
+        self.0.render()
+    }
+
+    fn finalize_state(&self) -> State {
+        self.0.clone()
+    }
+}
+
+pub trait SkipControlTrait: 'static + LimitControlTrait + FinalizeTrait {
+    /// **SKIP** query function, a **LIMIT** can follow it:
+    ///
+    /// `SKIP 20 LIMIT 10`
+    fn skip(&self, value: usize) -> Box<dyn LimitControlTrait>;
+}
+
+pub struct SkipControlQuery(State);
+
+impl LimitControlTrait for SkipControlQuery {
+    fn limit(&self, value: usize) -> Box<dyn FinalizeTrait> {
+        let mut state = self.0.clone();
+        state.push(Clause::Limit(value));
+        Box::new(Finalize(state))
+    }
+}
+
+impl SkipControlTrait for SkipControlQuery {
+    fn skip(&self, value: usize) -> Box<dyn LimitControlTrait> {
+        let mut state = self.0.clone();
+        state.push(Clause::Skip(value));
+        Box::new(LimitControlQuery(state))
+    }
+}
+
 impl FinalizeTrait for SkipControlQuery {
     fn finalize(&self) -> String {
+        self.0.query()
+    }
+
+    fn finalize_with_params(&self) -> (String, Params) {
+        self.0.render()
+    }
+
+    fn finalize_state(&self) -> State {
         self.0.clone()
     }
 }
 
-pub trait LimitControlTrait: 'static + SkipControlTrait + FinalizeTrait {
-    fn limit(&self, value: usize) -> Box<dyn SkipControlTrait>;
+/// Sort order of an **ORDER BY** item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Order {
+    /// `ASC`
+    Asc,
+    /// `DESC`
+    Desc,
 }
 
-pub struct LimitControlQuery(String);
+impl std::fmt::Display for Order {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Order::Asc => write!(f, "ASC"),
+            Order::Desc => write!(f, "DESC"),
+        }
+    }
+}
 
-impl SkipControlTrait for LimitControlQuery {
-    fn skip(&self, value: usize) -> Box<dyn FinalizeTrait> {
-        let state = format!(
-            "{prev_state}\nSKIP {value}",
-            prev_state = self.0,
-            value = value
-        );
+pub trait OrderControlTrait: 'static + SkipControlTrait + FinalizeTrait {
+    /// **ORDER BY** query function.
+    ///
+    /// Items are either properties of returned variables or aliases
+    /// introduced with `r#as`:
+    ///
+    /// `ORDER BY n.age DESC, name ASC`
+    fn order_by(&self, items: Vec<(Expr, Order)>) -> Box<dyn SkipControlTrait>;
+}
+
+pub struct OrderControlQuery(State);
+
+impl OrderControlTrait for OrderControlQuery {
+    fn order_by(&self, items: Vec<(Expr, Order)>) -> Box<dyn SkipControlTrait> {
+        order_by_method(&self.0, items)
+    }
+}
+
+impl LimitControlTrait for OrderControlQuery {
+    fn limit(&self, value: usize) -> Box<dyn FinalizeTrait> {
+        let mut state = self.0.clone();
+        state.push(Clause::Limit(value));
         Box::new(Finalize(state))
     }
 }
 
-impl LimitControlTrait for LimitControlQuery {
-    fn limit(&self, value: usize) -> Box<dyn SkipControlTrait> {
-        let state = format!(
-            "{prev_state}\nLIMIT {value}",
-            prev_state = self.0,
-            value = value
-        );
-        Box::new(SkipControlQuery(state))
+impl SkipControlTrait for OrderControlQuery {
+    fn skip(&self, value: usize) -> Box<dyn LimitControlTrait> {
+        let mut state = self.0.clone();
+        state.push(Clause::Skip(value));
+        Box::new(LimitControlQuery(state))
     }
 }
 
-impl FinalizeTrait for LimitControlQuery {
+impl FinalizeTrait for OrderControlQuery {
     fn finalize(&self) -> String {
+        self.0.query()
+    }
+
+    fn finalize_with_params(&self) -> (String, Params) {
+        self.0.render()
+    }
+
+    fn finalize_state(&self) -> State {
         self.0.clone()
     }
 }
 
-pub trait ReturnParamTrait: 'static + LimitControlTrait + FinalizeTrait {
-    fn r#as(&self, r#as: &str) -> Box<dyn LimitControlTrait>;
+pub trait ReturnParamTrait: 'static + OrderControlTrait + FinalizeTrait {
+    /// Alias of the last returned item: `RETURN n.name,n.age AS age`
+    ///
+    /// To alias several items, pass them to `return_items` instead.
+    fn r#as(&self, r#as: &str) -> Box<dyn OrderControlTrait>;
+
+    /// Turn the projection into **RETURN DISTINCT**.
+    fn distinct(&self) -> Box<dyn ReturnParamTrait>;
 }
 
 pub struct ReturnParamQuery {
-    state: String,
+    state: State,
 }
 
 impl ReturnParamQuery {
-    pub fn new(state: String) -> Self {
+    pub fn new(state: State) -> Self {
         Self { state }
     }
 }
 
 impl LimitControlTrait for ReturnParamQuery {
-    fn limit(&self, value: usize) -> Box<dyn SkipControlTrait> {
-        let state = format!(
-            "{prev_state}\nLIMIT {value}",
-            prev_state = self.state,
-            value = value
-        );
-        Box::new(SkipControlQuery(state))
+    fn limit(&self, value: usize) -> Box<dyn FinalizeTrait> {
+        let mut state = self.state.clone();
+        state.push(Clause::Limit(value));
+        Box::new(Finalize(state))
     }
 }
 
 impl SkipControlTrait for ReturnParamQuery {
-    fn skip(&self, value: usize) -> Box<dyn FinalizeTrait> {
-        let state = format!(
-            "{prev_state}\nSKIP {value}",
-            prev_state = self.state,
-            value = value
-        );
-        Box::new(Finalize(state))
+    fn skip(&self, value: usize) -> Box<dyn LimitControlTrait> {
+        let mut state = self.state.clone();
+        state.push(Clause::Skip(value));
+        Box::new(LimitControlQuery(state))
+    }
+}
+
+impl OrderControlTrait for ReturnParamQuery {
+    fn order_by(&self, items: Vec<(Expr, Order)>) -> Box<dyn SkipControlTrait> {
+        order_by_method(&self.state, items)
     }
 }
 
 impl ReturnParamTrait for ReturnParamQuery {
-    fn r#as(&self, r#as: &str) -> Box<dyn LimitControlTrait> {
-        let state = format!(
-            "{prev_state} AS {as_val}",
-            prev_state = self.state,
-            as_val = r#as
-        );
-        Box::new(LimitControlQuery(state))
+    fn r#as(&self, r#as: &str) -> Box<dyn OrderControlTrait> {
+        let mut state = self.state.clone();
+        if let Some(Clause::Return { items, .. }) = state.clauses_mut().last_mut() {
+            if let Some(item) = items.last_mut() {
+                item.alias = Some(r#as.to_string());
+            }
+        }
+        Box::new(OrderControlQuery(state))
+    }
+
+    fn distinct(&self) -> Box<dyn ReturnParamTrait> {
+        let mut state = self.state.clone();
+        if let Some(Clause::Return { distinct, .. }) = state.clauses_mut().last_mut() {
+            *distinct = true;
+        }
+        Box::new(ReturnParamQuery::new(state))
     }
 }
 
 impl FinalizeTrait for ReturnParamQuery {
     fn finalize(&self) -> String {
+        self.state.query()
+    }
+
+    fn finalize_with_params(&self) -> (String, Params) {
+        self.state.render()
+    }
+
+    fn finalize_state(&self) -> State {
         self.state.clone()
     }
 }
@@ -114,14 +216,29 @@ pub trait ReturnTrait: 'static + FinalizeTrait {
     fn r#return(&mut self, nv: &str) -> Box<dyn ReturnParamTrait>;
     fn return_many(&mut self, nvs: Vec<&str>) -> Box<dyn ReturnParamTrait>;
     fn return_field(&mut self, nv: &str, field: &str) -> Box<dyn ReturnParamTrait>;
+
+    /// **RETURN** of arbitrary expressions, each with an optional alias.
+    /// Aggregations can be mixed with the grouping keys:
+    ///
+    /// `RETURN n.city AS city,count(*) AS total`
+    fn return_items(&mut self, items: Vec<ReturnItem>) -> Box<dyn ReturnParamTrait>;
+
+    /// **RETURN** of all variables in scope: `RETURN *`
+    fn return_all(&mut self) -> Box<dyn ReturnParamTrait>;
+
+    /// **WITH** query function, projects variables and expressions
+    /// for the next part of the query:
+    ///
+    /// `WITH n, n.age AS age`
+    fn with(&mut self, items: Vec<ReturnItem>) -> Box<dyn WithTrait>;
 }
 
 pub struct ReturnQuery {
-    state: String,
+    state: State,
 }
 
 impl ReturnQuery {
-    pub fn new(state: String) -> Self {
+    pub fn new(state: State) -> Self {
         ReturnQuery { state }
     }
 }
@@ -138,41 +255,68 @@ impl ReturnTrait for ReturnQuery {
     fn return_many(&mut self, nvs: Vec<&str>) -> Box<dyn ReturnParamTrait> {
         return_method(&self.state, nvs, None)
     }
+
+    fn return_items(&mut self, items: Vec<ReturnItem>) -> Box<dyn ReturnParamTrait> {
+        return_items_method(&self.state, items)
+    }
+
+    fn return_all(&mut self) -> Box<dyn ReturnParamTrait> {
+        return_items_method(&self.state, vec![Expr::all().into()])
+    }
+
+    fn with(&mut self, items: Vec<ReturnItem>) -> Box<dyn WithTrait> {
+        with_method(&self.state, items)
+    }
 }
 
 impl FinalizeTrait for ReturnQuery {
     fn finalize(&self) -> String {
+        self.state.query()
+    }
+
+    fn finalize_with_params(&self) -> (String, Params) {
+        self.state.render()
+    }
+
+    fn finalize_state(&self) -> State {
         self.state.clone()
     }
 }
 
 pub(super) fn return_method(
-    state: &str,
+    state: &State,
     nvs: Vec<&str>,
     field: Option<&str>,
 ) -> Box<dyn ReturnParamTrait> {
-    let state = if nvs.len() > 1 {
-        let mut state = format!("{prev_state}\nRETURN ", prev_state = state);
-        state.push_str(&(nvs.iter().map(|nv| format!("{},", nv)).collect::<String>()));
-        state.pop();
-
-        state
+    let items = if nvs.len() > 1 {
+        nvs.iter().map(|nv| Expr::var(nv)).collect::<Vec<_>>()
+    } else if let Some(field) = field {
+        vec![Expr::prop(nvs[0], field)]
     } else {
-        if let Some(field) = field {
-            format!(
-                "{prev_state}\nRETURN {node_var}.{prop_name}",
-                prev_state = state,
-                node_var = nvs[0],
-                prop_name = field
-            )
-        } else {
-            format!(
-                "{prev_state}\nRETURN {node_var}",
-                prev_state = state,
-                node_var = nvs[0]
-            )
-        }
+        vec![Expr::var(nvs[0])]
     };
 
+    return_items_method(state, items.into_iter().map(ReturnItem::from).collect())
+}
+
+pub(super) fn return_items_method(
+    state: &State,
+    items: Vec<ReturnItem>,
+) -> Box<dyn ReturnParamTrait> {
+    let mut state = state.clone();
+    state.push(Clause::Return {
+        distinct: false,
+        items,
+    });
+
     Box::new(ReturnParamQuery::new(state))
 }
+
+fn order_by_method(state: &State, items: Vec<(Expr, Order)>) -> Box<dyn SkipControlTrait> {
+    let mut state = state.clone();
+    if !items.is_empty() {
+        state.push(Clause::OrderBy(items));
+    }
+
+    Box::new(SkipControlQuery(state))
+}
@@ -0,0 +1,71 @@
+use crate::entity::Props;
+use crate::query::ast::Clause;
+use crate::query::render::render;
+
+/// Parameters referenced by a query as `$name` placeholders.
+pub type Params = Props;
+
+/// The clauses collected so far, passed from one builder to the next.
+///
+/// When the query was started in parameter mode, values are not inlined
+/// into the text. Each of them is registered in the parameter map and
+/// replaced by a `$p0`, `$p1`, ... placeholder.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct State {
+    clauses: Vec<Clause>,
+    with_params: bool,
+}
+
+impl State {
+    /// State of a query with all values inlined as literals.
+    pub fn new(query: String) -> Self {
+        State {
+            clauses: Self::raw(query),
+            with_params: false,
+        }
+    }
+
+    /// State of a query in parameter mode.
+    pub fn with_params(query: String) -> Self {
+        State {
+            clauses: Self::raw(query),
+            with_params: true,
+        }
+    }
+
+    fn raw(query: String) -> Vec<Clause> {
+        if query.is_empty() {
+            Vec::new()
+        } else {
+            vec![Clause::Raw(query)]
+        }
+    }
+
+    pub fn clauses(&self) -> &[Clause] {
+        &self.clauses
+    }
+
+    /// Mutable access to the clauses, for rewriting a query before it is rendered.
+    pub fn clauses_mut(&mut self) -> &mut Vec<Clause> {
+        &mut self.clauses
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.clauses.is_empty()
+    }
+
+    /// Append a clause.
+    pub(crate) fn push(&mut self, clause: Clause) {
+        self.clauses.push(clause);
+    }
+
+    /// The query text.
+    pub fn query(&self) -> String {
+        self.render().0
+    }
+
+    /// The query text together with its parameters.
+    pub fn render(&self) -> (String, Params) {
+        render(&self.clauses, self.with_params)
+    }
+}
@@ -0,0 +1,155 @@
+use crate::entity::Entity;
+use crate::query::ast::{Clause, Expr, Pattern, Projection, ReturnItem};
+use crate::query::finalize::FinalizeTrait;
+use crate::query::match_query::{MatchConditionTrait, MatchTrait};
+use crate::query::merge_query::MergeTrait;
+use crate::query::return_query::{Order, ReturnParamTrait, ReturnTrait};
+use crate::query::state::{Params, State};
+use crate::query::{PropsMatch, QueryTrait};
+
+pub trait WithTrait: 'static + ReturnTrait + QueryTrait {
+    /// Turn the projection into **WITH DISTINCT**.
+    fn distinct(&self) -> Box<dyn WithTrait>;
+
+    /// **ORDER BY** of the projected rows:
+    ///
+    /// `WITH n, n.age AS age ORDER BY age DESC`
+    fn order_by(&self, items: Vec<(Expr, Order)>) -> Box<dyn WithTrait>;
+
+    /// **SKIP** of the projected rows.
+    fn skip(&self, value: usize) -> Box<dyn WithTrait>;
+
+    /// **LIMIT** of the projected rows.
+    fn limit(&self, value: usize) -> Box<dyn WithTrait>;
+
+    /// **WHERE** filter on the projected variables, written after the rest of
+    /// the projection. Repeated calls are joined with **AND**:
+    ///
+    /// `WITH n, n.age AS age WHERE age > 18`
+    fn r#where(&self, expr: Expr) -> Box<dyn WithTrait>;
+}
+
+pub struct WithQuery {
+    state: State,
+}
+
+impl WithQuery {
+    pub fn new(state: State) -> Self {
+        WithQuery { state }
+    }
+
+    /// Change the last **WITH** clause.
+    fn update<F: FnOnce(&mut Projection)>(&self, f: F) -> Box<dyn WithTrait> {
+        let mut state = self.state.clone();
+        if let Some(Clause::With(projection)) = state.clauses_mut().last_mut() {
+            f(projection);
+        }
+
+        Box::new(Self::new(state))
+    }
+}
+
+impl WithTrait for WithQuery {
+    fn distinct(&self) -> Box<dyn WithTrait> {
+        self.update(|projection| projection.distinct = true)
+    }
+
+    fn order_by(&self, items: Vec<(Expr, Order)>) -> Box<dyn WithTrait> {
+        self.update(|projection| projection.order.extend(items))
+    }
+
+    fn skip(&self, value: usize) -> Box<dyn WithTrait> {
+        self.update(|projection| projection.skip = Some(value))
+    }
+
+    fn limit(&self, value: usize) -> Box<dyn WithTrait> {
+        self.update(|projection| projection.limit = Some(value))
+    }
+
+    fn r#where(&self, expr: Expr) -> Box<dyn WithTrait> {
+        self.update(|projection| {
+            projection.predicate = Some(match projection.predicate.take() {
+                Some(prev) => prev.and(expr),
+                None => expr,
+            })
+        })
+    }
+}
+
+impl FinalizeTrait for WithQuery {
+    fn finalize(&self) -> String {
+        self.state.query()
+    }
+
+    fn finalize_with_params(&self) -> (String, Params) {
+        self.state.render()
+    }
+
+    fn finalize_state(&self) -> State {
+        self.state.clone()
+    }
+}
+
+impl ReturnTrait for WithQuery {
+    fn r#return(&mut self, nv: &str) -> Box<dyn ReturnParamTrait> {
+        super::return_query::return_method(&self.state, vec![nv], None)
+    }
+
+    fn return_field(&mut self, nv: &str, field: &str) -> Box<dyn ReturnParamTrait> {
+        super::return_query::return_method(&self.state, vec![nv], Some(field))
+    }
+
+    fn return_many(&mut self, nvs: Vec<&str>) -> Box<dyn ReturnParamTrait> {
+        super::return_query::return_method(&self.state, nvs, None)
+    }
+
+    fn return_items(&mut self, items: Vec<ReturnItem>) -> Box<dyn ReturnParamTrait> {
+        super::return_query::return_items_method(&self.state, items)
+    }
+
+    fn return_all(&mut self) -> Box<dyn ReturnParamTrait> {
+        super::return_query::return_items_method(&self.state, vec![Expr::all().into()])
+    }
+
+    fn with(&mut self, items: Vec<ReturnItem>) -> Box<dyn WithTrait> {
+        with_method(&self.state, items)
+    }
+}
+
+impl QueryTrait for WithQuery {
+    fn create(&mut self, entitys: Vec<&Entity>) -> Box<dyn ReturnTrait> {
+        super::create_method(&self.state, entitys)
+    }
+
+    fn r#match(&mut self, entity: &Entity, optional: bool) -> Box<dyn MatchTrait> {
+        super::match_method(&self.state, entity, optional)
+    }
+
+    fn match_pattern(&mut self, pattern: Pattern, optional: bool) -> Box<dyn MatchTrait> {
+        super::match_pattern_method(&self.state, pattern, optional)
+    }
+
+    fn match_props(
+        &mut self,
+        entity: &Entity,
+        mode: PropsMatch,
+        fields: Option<Vec<&str>>,
+        optional: bool,
+    ) -> Box<dyn MatchConditionTrait> {
+        super::match_props_method(&self.state, entity, mode, fields, optional)
+    }
+
+    fn merge(&mut self, entity: &Entity) -> Box<dyn MergeTrait> {
+        super::merge_method(&self.state, entity)
+    }
+}
+
+pub(super) fn with_method(state: &State, items: Vec<ReturnItem>) -> Box<dyn WithTrait> {
+    let mut state = state.clone();
+    state.push(Clause::With(Projection {
+        items,
+        ..Default::default()
+    }));
+
+    Box::new(WithQuery::new(state))
+}
@@ -0,0 +1,180 @@
+//! Conversion of date and time types from `std` and the `chrono` and `time` crates
+//! into Neo4j temporal properties.
+//!
+//! Each conversion is enabled by the cargo feature of the same name, only
+//! `std::time::Duration` is always available. Fields of these types in a
+//! `#[derive(CypQue)]` struct are converted automatically.
+
+use crate::entity::PropType;
+
+/// ISO 8601 time of day, with the fraction of a second only if there is one.
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn iso_time(hour: u32, minute: u32, second: u32, nanos: u32) -> String {
+    let mut time = format!("{hour:02}:{minute:02}:{second:02}");
+    if nanos > 0 {
+        time.push_str(format!(".{nanos:09}").trim_end_matches('0'));
+    }
+
+    time
+}
+
+/// ISO 8601 duration of an exact amount of seconds: `PT90.5S` or `-PT1S`.
+fn iso_duration(negative: bool, seconds: u64, nanos: u32) -> String {
+    let sign = if negative { "-" } else { "" };
+    let mut duration = format!("{sign}PT{seconds}");
+    if nanos > 0 {
+        duration.push_str(format!(".{nanos:09}").trim_end_matches('0'));
+    }
+    duration.push('S');
+
+    duration
+}
+
+impl From<std::time::Duration> for PropType {
+    fn from(value: std::time::Duration) -> Self {
+        PropType::Duration(iso_duration(false, value.as_secs(), value.subsec_nanos()))
+    }
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_impl {
+    use chrono::{
+        Datelike, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeDelta, TimeZone, Timelike,
+    };
+
+    use super::{iso_duration, iso_time, PropType};
+
+    fn date(value: &NaiveDate) -> String {
+        format!(
+            "{:04}-{:02}-{:02}",
+            value.year(),
+            value.month(),
+            value.day()
+        )
+    }
+
+    fn time(value: &NaiveTime) -> String {
+        iso_time(
+            value.hour(),
+            value.minute(),
+            value.second(),
+            value.nanosecond(),
+        )
+    }
+
+    impl From<NaiveDate> for PropType {
+        fn from(value: NaiveDate) -> Self {
+            PropType::Date(date(&value))
+        }
+    }
+
+    impl From<NaiveTime> for PropType {
+        fn from(value: NaiveTime) -> Self {
+            PropType::LocalTime(time(&value))
+        }
+    }
+
+    impl From<NaiveDateTime> for PropType {
+        fn from(value: NaiveDateTime) -> Self {
+            PropType::LocalDateTime(format!("{}T{}", date(&value.date()), time(&value.time())))
+        }
+    }
+
+    impl<Tz: TimeZone> From<chrono::DateTime<Tz>> for PropType {
+        fn from(value: chrono::DateTime<Tz>) -> Self {
+            let local = value.naive_local();
+            let offset = value.offset().fix().local_minus_utc();
+            let sign = if offset < 0 { '-' } else { '+' };
+            let offset = offset.unsigned_abs();
+
+            PropType::DateTime(format!(
+                "{}T{}{sign}{:02}:{:02}",
+                date(&local.date()),
+                time(&local.time()),
+                offset / 3600,
+                offset % 3600 / 60,
+            ))
+        }
+    }
+
+    impl From<TimeDelta> for PropType {
+        fn from(value: TimeDelta) -> Self {
+            let negative = value < TimeDelta::zero();
+            let value = value.abs();
+
+            PropType::Duration(iso_duration(
+                negative,
+                value.num_seconds() as u64,
+                value.subsec_nanos() as u32,
+            ))
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_impl {
+    use time::{Date, Duration, OffsetDateTime, PrimitiveDateTime, Time};
+
+    use super::{iso_duration, iso_time, PropType};
+
+    fn date(value: &Date) -> String {
+        format!(
+            "{:04}-{:02}-{:02}",
+            value.year(),
+            u8::from(value.month()),
+            value.day()
+        )
+    }
+
+    fn time(value: &Time) -> String {
+        iso_time(
+            value.hour().into(),
+            value.minute().into(),
+            value.second().into(),
+            value.nanosecond(),
+        )
+    }
+
+    impl From<Date> for PropType {
+        fn from(value: Date) -> Self {
+            PropType::Date(date(&value))
+        }
+    }
+
+    impl From<Time> for PropType {
+        fn from(value: Time) -> Self {
+            PropType::LocalTime(time(&value))
+        }
+    }
+
+    impl From<PrimitiveDateTime> for PropType {
+        fn from(value: PrimitiveDateTime) -> Self {
+            PropType::LocalDateTime(format!("{}T{}", date(&value.date()), time(&value.time())))
+        }
+    }
+
+    impl From<OffsetDateTime> for PropType {
+        fn from(value: OffsetDateTime) -> Self {
+            let offset = value.offset();
+            let sign = if offset.is_negative() { '-' } else { '+' };
+
+            PropType::DateTime(format!(
+                "{}T{}{sign}{:02}:{:02}",
+                date(&value.date()),
+                time(&value.time()),
+                offset.whole_hours().unsigned_abs(),
+                offset.minutes_past_hour().unsigned_abs(),
+            ))
+        }
+    }
+
+    impl From<Duration> for PropType {
+        fn from(value: Duration) -> Self {
+            PropType::Duration(iso_duration(
+                value.is_negative(),
+                value.whole_seconds().unsigned_abs(),
+                value.subsec_nanoseconds().unsigned_abs(),
+            ))
+        }
+    }
+}
@@ -0,0 +1,345 @@
+use neo4j_cypher::entity::{Entity, Node, PropType};
+use neo4j_cypher::query::ast::{Clause, Expr, NodePattern, Pattern, ReturnItem};
+use neo4j_cypher::query::match_query::CompOper;
+use neo4j_cypher::query::return_query::Order;
+use neo4j_cypher::query::{Query, QueryTrait};
+
+fn profile() -> Entity<'static> {
+    Node::new("n", "Profile", None, None).into()
+}
+
+#[test]
+fn builders_produce_clauses() {
+    let state = Query::init()
+        .r#match(&profile(), false)
+        .r#where("age", CompOper::More, PropType::int(18))
+        .or("name", CompOper::Equal, PropType::str("admin"))
+        .and("age", CompOper::Less, PropType::int(99))
+        .r#return("n")
+        .finalize_state();
+
+    let compare = |key: &str, op, value| {
+        Expr::Compare(
+            Box::new(Expr::prop("n", key)),
+            op,
+            Box::new(Expr::Value(value)),
+        )
+    };
+
+    assert_eq!(
+        state.clauses(),
+        &[
+            Clause::Match {
+                optional: false,
+                pattern: Pattern::Node(NodePattern {
+                    var: Some(String::from("n")),
+                    labels: vec![String::from("Profile")],
+                    label_expr: None,
+                    props: Vec::new(),
+                }),
+                // `AND` binds tighter than the `OR` written before it.
+                predicate: Some(Expr::Or(
+                    Box::new(compare("age", CompOper::More, PropType::int(18))),
+                    Box::new(Expr::And(
+                        Box::new(compare("name", CompOper::Equal, PropType::str("admin"))),
+                        Box::new(compare("age", CompOper::Less, PropType::int(99))),
+                    )),
+                )),
+            },
+            Clause::Return {
+                distinct: false,
+                items: vec![ReturnItem {
+                    expr: Expr::var("n"),
+                    alias: None,
+                }],
+            },
+        ]
+    );
+}
+
+#[test]
+fn rewritten_state_is_rendered() {
+    let mut state = Query::init_with_params()
+        .r#match(&profile(), false)
+        .r#where("name", CompOper::Equal, PropType::str("admin"))
+        .r#return("n")
+        .finalize_state();
+
+    state.clauses_mut().push(Clause::Limit(10));
+    let (query, params) = state.render();
+
+    assert_eq!(
+        query,
+        "MATCH (n:Profile) WHERE n.name = $p0\nRETURN n\nLIMIT 10"
+    );
+    assert_eq!(params["p0"], PropType::str("admin"));
+}
+
+#[test]
+fn conditions_are_parenthesized_by_precedence() {
+    let x = || Expr::cond("n", "x", CompOper::Equal, PropType::int(1));
+    let y = || Expr::cond("n", "y", CompOper::Equal, PropType::int(2));
+    let z = || Expr::cond("n", "z", CompOper::Equal, PropType::int(3));
+
+    let cases = [
+        (
+            x().or(y()).and(!z()),
+            "(n.x = 1 OR n.y = 2) AND NOT n.z = 3",
+        ),
+        (x().and(y()).or(z()), "n.x = 1 AND n.y = 2 OR n.z = 3"),
+        (x().xor(y().or(z())), "n.x = 1 XOR (n.y = 2 OR n.z = 3)"),
+        (x().or(y()).xor(z()), "(n.x = 1 OR n.y = 2) XOR n.z = 3"),
+        (!x().and(y()), "NOT (n.x = 1 AND n.y = 2)"),
+        (!!x(), "NOT NOT n.x = 1"),
+        (
+            x().and(y()).group().or(z()),
+            "(n.x = 1 AND n.y = 2) OR n.z = 3",
+        ),
+    ];
+
+    for (expr, predicate) in cases {
+        let query = Query::init()
+            .r#match(&profile(), false)
+            .where_expr(expr)
+            .finalize();
+
+        assert_eq!(query, format!("MATCH (n:Profile) WHERE {}", predicate));
+    }
+}
+
+#[test]
+fn comparison_operators() {
+    let names = || PropType::Array(vec![PropType::str("a"), PropType::str("b")]);
+    let cases = [
+        (CompOper::NotEqual, PropType::int(1), "n.p <> 1"),
+        (CompOper::In, names(), "n.p IN ['a','b']"),
+        (
+            CompOper::StartsWith,
+            PropType::str("a"),
+            "n.p STARTS WITH 'a'",
+        ),
+        (CompOper::EndsWith, PropType::str("a"), "n.p ENDS WITH 'a'"),
+        (CompOper::Contains, PropType::str("a"), "n.p CONTAINS 'a'"),
+        (
+            CompOper::Regex,
+            PropType::str("(?i)a.*"),
+            "n.p =~ '(?i)a.*'",
+        ),
+        (CompOper::IsNull, PropType::Null, "n.p IS NULL"),
+        (CompOper::IsNotNull, PropType::Null, "n.p IS NOT NULL"),
+    ];
+
+    for (op, value, predicate) in cases {
+        let query = Query::init()
+            .r#match(&profile(), false)
+            .r#where("p", op, value)
+            .finalize();
+
+        assert_eq!(query, format!("MATCH (n:Profile) WHERE {}", predicate));
+    }
+}
+
+#[test]
+fn null_checks_have_no_parameter() {
+    let (query, params) = Query::init_with_params()
+        .r#match(&profile(), false)
+        .r#where("p", CompOper::IsNull, PropType::Null)
+        .and("q", CompOper::In, PropType::Array(vec![PropType::int(1)]))
+        .finalize_with_params();
+
+    assert_eq!(query, "MATCH (n:Profile) WHERE n.p IS NULL AND n.q IN $p0");
+    assert_eq!(params.len(), 1);
+}
+
+#[test]
+fn typed_null_and_list_conditions() {
+    let query = Query::init()
+        .r#match(&profile(), false)
+        .where_is_null("p")
+        .and_in("q", vec![PropType::int(1), PropType::str("a")])
+        .or_is_not_null_var("m", "r")
+        .or_in_var("m", "s", Vec::new())
+        .finalize();
+
+    assert_eq!(
+        query,
+        "MATCH (n:Profile) WHERE n.p IS NULL AND n.q IN [1,'a'] OR m.r IS NOT NULL OR m.s IN []"
+    );
+}
+
+#[test]
+fn mismatched_operands_are_rejected() {
+    let cases = vec![
+        (CompOper::In, PropType::int(1)),
+        (CompOper::StartsWith, PropType::int(1)),
+        (CompOper::Regex, PropType::Array(Vec::new())),
+        (CompOper::IsNull, PropType::str("x")),
+        (CompOper::IsNotNull, PropType::Bool(false)),
+    ];
+
+    for (op, value) in cases {
+        let err = Expr::prop("n", "p").try_compare(op, value).unwrap_err();
+        assert_eq!(err.op, op);
+    }
+
+    let err = Expr::prop("n", "p")
+        .try_compare(CompOper::In, PropType::str("a"))
+        .unwrap_err();
+    assert_eq!(err.to_string(), "operator `IN` expects a list, got 'a'");
+    assert!(Expr::prop("n", "p")
+        .try_compare(CompOper::Equal, PropType::Null)
+        .is_ok());
+}
+
+#[test]
+#[should_panic(expected = "operator `CONTAINS` expects a string, got 3")]
+fn mismatched_operand_panics() {
+    Query::init()
+        .r#match(&profile(), false)
+        .r#where("p", CompOper::Contains, PropType::int(3));
+}
+
+#[test]
+fn order_by_property_and_alias() {
+    let query = Query::init()
+        .r#match(&profile(), false)
+        .r#where("age", CompOper::IsNotNull, PropType::Null)
+        .return_field("n", "name")
+        .r#as("name")
+        .order_by(vec![
+            (Expr::prop("n", "age"), Order::Desc),
+            (Expr::var("name"), Order::Asc),
+        ])
+        .skip(20)
+        .limit(10)
+        .finalize();
+
+    assert_eq!(
+        query,
+        "MATCH (n:Profile) WHERE n.age IS NOT NULL\nRETURN n.name AS name\nORDER BY n.age DESC, name ASC\nSKIP 20\nLIMIT 10"
+    );
+}
+
+#[test]
+fn skip_then_limit_without_order() {
+    let query = Query::init()
+        .r#match(&profile(), false)
+        .r#where("online", CompOper::Equal, PropType::Bool(true))
+        .r#return("n")
+        .skip(5)
+        .limit(3)
+        .finalize();
+
+    assert_eq!(
+        query,
+        "MATCH (n:Profile) WHERE n.online = true\nRETURN n\nSKIP 5\nLIMIT 3"
+    );
+}
+
+#[test]
+fn with_projection_continues_the_query() {
+    let friend: Entity = Node::new("f", "Profile", None, None).into();
+    let query = Query::init()
+        .r#match(&profile(), false)
+        .with(vec![
+            Expr::var("n").into(),
+            Expr::prop("n", "age").r#as("age"),
+        ])
+        .limit(10)
+        .r#where(Expr::var("age").compare(CompOper::More, PropType::int(18)))
+        .order_by(vec![(Expr::var("age"), Order::Desc)])
+        .distinct()
+        .skip(5)
+        .r#match(&friend, false)
+        .r#where("age", CompOper::Less, PropType::int(30))
+        .return_many(vec!["n", "f"])
+        .finalize();
+
+    assert_eq!(
+        query,
+        "MATCH (n:Profile)\n\
+         WITH DISTINCT n,n.age AS age ORDER BY age DESC SKIP 5 LIMIT 10 WHERE age > 18\n\
+         MATCH (f:Profile) WHERE f.age < 30\n\
+         RETURN n,f"
+    );
+}
+
+#[test]
+fn aggregations_with_grouping_keys() {
+    let age = || Expr::prop("n", "age");
+    let query = Query::init()
+        .r#match(&profile(), false)
+        .where_expr(age().compare(CompOper::IsNotNull, PropType::Null))
+        .return_items(vec![
+            Expr::prop("n", "city").r#as("city"),
+            Expr::count_all().r#as("total"),
+            Expr::count(Expr::prop("n", "name"))
+                .distinct()
+                .r#as("names"),
+            Expr::sum(age()).into(),
+            Expr::avg(age()).into(),
+            Expr::min(age()).into(),
+            Expr::max(age()).into(),
+            Expr::collect(Expr::prop("n", "name")).into(),
+            Expr::percentile_cont(age(), 0.5).into(),
+            Expr::percentile_disc(age(), 0.9).into(),
+            Expr::st_dev(age()).r#as("spread"),
+        ])
+        .finalize();
+
+    assert_eq!(
+        query,
+        "MATCH (n:Profile) WHERE n.age IS NOT NULL\n\
+         RETURN n.city AS city,count(*) AS total,count(DISTINCT n.name) AS names,\
+         sum(n.age),avg(n.age),min(n.age),max(n.age),collect(n.name),\
+         percentileCont(n.age, 0.5),percentileDisc(n.age, 0.9),stDev(n.age) AS spread"
+    );
+}
+
+#[test]
+fn whole_number_percentiles_are_floats() {
+    let (query, params) = Query::init_with_params()
+        .r#match(&profile(), false)
+        .r#where("age", CompOper::More, PropType::int(18))
+        .return_items(vec![
+            Expr::percentile_cont(Expr::prop("n", "age"), 1.0).into(),
+            Expr::percentile_disc(Expr::prop("n", "age"), 0.0).into(),
+        ])
+        .finalize_with_params();
+
+    assert_eq!(
+        query,
+        "MATCH (n:Profile) WHERE n.age > $p0\n\
+         RETURN percentileCont(n.age, 1.0),percentileDisc(n.age, 0.0)"
+    );
+    assert_eq!(params.len(), 1);
+}
+
+#[test]
+fn return_distinct_all_and_aliases() {
+    let query = || {
+        Query::init()
+            .r#match(&profile(), false)
+            .where_expr(Expr::prop("n", "age").compare(CompOper::More, PropType::int(18)))
+    };
+
+    assert_eq!(
+        query()
+            .return_items(vec![
+                Expr::prop("n", "name").r#as("name"),
+                Expr::prop("n", "age").r#as("age"),
+                Expr::var("n").into(),
+            ])
+            .distinct()
+            .finalize(),
+        "MATCH (n:Profile) WHERE n.age > 18\nRETURN DISTINCT n.name AS name,n.age AS age,n"
+    );
+    assert_eq!(
+        query().return_all().finalize(),
+        "MATCH (n:Profile) WHERE n.age > 18\nRETURN *"
+    );
+    assert_eq!(
+        query().return_many(vec!["n", "m"]).r#as("x").finalize(),
+        "MATCH (n:Profile) WHERE n.age > 18\nRETURN n,m AS x"
+    );
+}
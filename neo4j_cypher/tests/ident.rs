@@ -0,0 +1,101 @@
+use neo4j_cypher::entity::{Entity, Label, Node, PropType, Props, Relation};
+use neo4j_cypher::ident::{Ident, IdentError};
+use neo4j_cypher::query::match_query::CompOper;
+use neo4j_cypher::query::{Query, QueryTrait};
+
+#[test]
+fn quoting() {
+    let cases = [
+        ("name", "name"),
+        ("_private1", "_private1"),
+        ("Order", "`Order`"),
+        ("match", "`match`"),
+        ("my label", "`my label`"),
+        ("1st", "`1st`"),
+        ("a`b", "`a``b`"),
+        ("Ünïcode", "`Ünïcode`"),
+    ];
+
+    for (name, quoted) in cases {
+        assert_eq!(Ident::new(name).unwrap().to_string(), quoted);
+    }
+}
+
+#[test]
+fn invalid_names() {
+    assert_eq!(Ident::new(""), Err(IdentError::Empty));
+    assert_eq!(
+        Ident::new("a\0b"),
+        Err(IdentError::NullChar(String::from("a\0b")))
+    );
+
+    assert!(Node::try_new("", "Profile", None, None).is_err());
+    assert!(Node::try_new("n", "", None, None).is_err());
+
+    let labels: Vec<Label> = vec![Box::new("Admin\0")];
+    assert!(Node::try_new("n", "Profile", None, Some(labels)).is_err());
+
+    let props = Props::from([(String::new(), PropType::int(1))]);
+    assert!(Node::try_new("n", "Profile", Some(props), None).is_err());
+
+    let node = Node::try_new("n", "Profile", None, None).unwrap();
+    assert!(node.try_with_keys(vec![""]).is_err());
+
+    let from = Node::new("a", "Profile", None, None);
+    let to = Node::new("b", "Profile", None, None);
+    assert!(Relation::try_new(from, to, "", None).is_err());
+}
+
+#[test]
+#[should_panic(expected = "identifier can not be empty")]
+fn new_panics_on_invalid_name() {
+    Node::new("", "Profile", None, None);
+}
+
+#[test]
+fn quoted_names_in_query() {
+    let labels: Vec<Label> = vec![Box::new("With Space")];
+    let props = Props::from([(String::from("order"), PropType::int(1))]);
+    let node = Node::new("n", "Order", Some(props), Some(labels));
+
+    let query = Query::init()
+        .create(vec![&node.into()])
+        .r#return("n")
+        .finalize();
+
+    assert_eq!(
+        query,
+        "CREATE (n:`Order`:`With Space` { `order`: 1 })\nRETURN n"
+    );
+}
+
+fn knows() -> Entity<'static> {
+    let from = Node::new("a", "Profile", None, None);
+    let to = Node::new("b", "Profile", None, None);
+    Relation::new(from, to, "KNOWS", None).into()
+}
+
+#[test]
+fn relation_match_uses_r() {
+    let query = Query::init()
+        .r#match(&knows(), false)
+        .r#where("since", CompOper::More, PropType::int(2020))
+        .r#return("r")
+        .finalize();
+
+    assert_eq!(
+        query,
+        "MATCH (a:Profile)-[r:KNOWS]->(b:Profile) WHERE r.since > 2020\nRETURN r"
+    );
+
+    let query = Query::init()
+        .r#match(&knows(), false)
+        .where_var("r", "since", CompOper::Less, PropType::int(2000))
+        .delete()
+        .finalize();
+
+    assert_eq!(
+        query,
+        "MATCH (a:Profile)-[r:KNOWS]->(b:Profile) WHERE r.since < 2000\nDELETE r"
+    );
+}
@@ -0,0 +1,101 @@
+use neo4j_cypher::entity::{Entity, Label, Node, PropType, Relation};
+use neo4j_cypher::query::ast::{Expr, LabelExpr, NodePattern, PathPattern, RelPattern};
+use neo4j_cypher::query::match_query::CompOper;
+use neo4j_cypher::query::{Query, QueryTrait};
+
+fn admin(nv: &str) -> Node<'static> {
+    let labels: Vec<Label> = vec![Box::new("Admin"), Box::new("Staff")];
+    Node::new(nv, "Profile", None, Some(labels))
+}
+
+#[test]
+fn match_uses_all_labels() {
+    let query = Query::init()
+        .r#match(&admin("n").into(), false)
+        .r#where("age", CompOper::More, PropType::int(18))
+        .r#return("n")
+        .finalize();
+
+    assert_eq!(
+        query,
+        "MATCH (n:Profile:Admin:Staff) WHERE n.age > 18\nRETURN n"
+    );
+
+    let rel: Entity = Relation::new(admin("a"), admin("b"), "KNOWS", None).into();
+    let query = Query::init()
+        .r#match(&rel, false)
+        .where_var("a", "age", CompOper::More, PropType::int(18))
+        .r#return("b")
+        .finalize();
+
+    assert!(query.starts_with("MATCH (a:Profile:Admin:Staff)-[r:KNOWS]->(b:Profile:Admin:Staff)"));
+}
+
+#[test]
+fn label_expressions() {
+    let a = || LabelExpr::label("A");
+    let b = || LabelExpr::label("B");
+    let cases = [
+        (NodePattern::var("n").label_expr(a().or(b())), "(n:A|B)"),
+        (
+            NodePattern::var("n").label_expr(!LabelExpr::label("Deleted")),
+            "(n:!Deleted)",
+        ),
+        (NodePattern::var("n").label_expr(LabelExpr::any()), "(n:%)"),
+        (NodePattern::var("n").label_expr(!a().or(b())), "(n:!(A|B))"),
+        (
+            NodePattern::var("n").label_expr(a().or(b()).and(!LabelExpr::label("C"))),
+            "(n:(A|B)&!C)",
+        ),
+        (
+            NodePattern::var("n")
+                .label("Profile")
+                .label_expr(a().or(b())),
+            "(n:Profile&(A|B))",
+        ),
+    ];
+
+    for (node, pattern) in cases {
+        let path = PathPattern::new(node).hop(RelPattern::outgoing(), NodePattern::var("m"));
+        let query = Query::init()
+            .match_pattern(path.into(), false)
+            .r#where("age", CompOper::More, PropType::int(18))
+            .r#return("n")
+            .finalize();
+
+        assert_eq!(
+            query,
+            format!("MATCH {}-[]->(m) WHERE n.age > 18\nRETURN n", pattern)
+        );
+    }
+}
+
+#[test]
+fn label_predicates() {
+    let query = Query::init()
+        .match_pattern(NodePattern::var("n").into(), false)
+        .where_expr(
+            Expr::has_labels("n", LabelExpr::label("Admin")).or(!Expr::has_labels(
+                "n",
+                LabelExpr::label("A").or(LabelExpr::label("B")),
+            )),
+        )
+        .r#return("n")
+        .finalize();
+
+    assert_eq!(query, "MATCH (n) WHERE n:Admin OR NOT n:A|B\nRETURN n");
+}
+
+#[test]
+fn create_writes_labels_into_pattern() {
+    let rel: Entity = Relation::new(admin("a"), admin("b"), "KNOWS", None).into();
+    let query = Query::init()
+        .create(vec![&admin("a").into(), &admin("b").into(), &rel])
+        .r#return("a")
+        .finalize();
+
+    assert_eq!(
+        query,
+        "CREATE (a:Profile:Admin:Staff),\n\t(b:Profile:Admin:Staff),\n\t(a)-[:KNOWS]->(b)\nRETURN a"
+    );
+}
@@ -0,0 +1,122 @@
+use neo4j_cypher::entity::{str_literal, Entity, Node, PropType, Props};
+use neo4j_cypher::query::match_query::CompOper;
+use neo4j_cypher::query::{Query, QueryTrait};
+
+const ADVERSARIAL: &[&str] = &[
+    "",
+    "O'Brien",
+    "x' OR 1=1 //",
+    "x'}) DETACH DELETE n //",
+    "\\",
+    "\\'",
+    "ends with backslash \\",
+    "\"double\" 'single' `back`",
+    "line\nbreak\r\nand\ttab",
+    "/* comment */ -- not a comment",
+    "\u{0}\u{1}\u{7}\u{8}\u{b}\u{c}\u{1b}\u{7f}\u{85}",
+    "\u{2028}\u{2029}",
+    "\\u0027 is a quote",
+    "юникод 日本語 🦀",
+];
+
+/// Parse a single-quoted Cypher string literal that must span the whole input
+/// and return its decoded content.
+fn parse_literal(input: &str) -> Result<String, String> {
+    let mut chars = input.chars();
+    if chars.next() != Some('\'') {
+        return Err(format!("literal must start with a quote: {}", input));
+    }
+
+    let mut value = String::new();
+    loop {
+        match chars.next() {
+            None => return Err(format!("unterminated literal: {}", input)),
+            Some('\'') => break,
+            Some('\\') => match chars.next() {
+                Some('\\') => value.push('\\'),
+                Some('\'') => value.push('\''),
+                Some('"') => value.push('"'),
+                Some('n') => value.push('\n'),
+                Some('r') => value.push('\r'),
+                Some('t') => value.push('\t'),
+                Some('b') => value.push('\u{8}'),
+                Some('f') => value.push('\u{c}'),
+                Some('u') => {
+                    let hex = chars.by_ref().take(4).collect::<String>();
+                    let code = u32::from_str_radix(&hex, 16).map_err(|e| e.to_string())?;
+                    value.push(char::from_u32(code).ok_or("invalid code point")?);
+                }
+                other => return Err(format!("invalid escape {:?} in {}", other, input)),
+            },
+            Some(ch) if ch.is_control() => {
+                return Err(format!("raw control character {:?} in {}", ch, input))
+            }
+            Some(ch) => value.push(ch),
+        }
+    }
+
+    match chars.next() {
+        None => Ok(value),
+        Some(_) => Err(format!("literal ends before the input does: {}", input)),
+    }
+}
+
+#[test]
+fn adversarial_strings_round_trip() {
+    for input in ADVERSARIAL {
+        let literal = str_literal(input);
+        assert_eq!(parse_literal(&literal).as_deref(), Ok(*input), "{}", literal);
+        assert_eq!(PropType::str(input.to_string()).to_prop(), literal);
+    }
+}
+
+#[test]
+fn arrays_escape_every_item() {
+    let prop = PropType::arr("String", vec!["a'b", "c\\"]).unwrap();
+    assert_eq!(prop.to_prop(), r"['a\'b','c\\']");
+}
+
+#[test]
+fn create_escapes_property_map() {
+    let mut props = Props::new();
+    props.insert("name".to_string(), PropType::str("x'}) DELETE n //"));
+    let node: Entity = Node::new("n", "Profile", Some(props), None).into();
+
+    let query = Query::init().create(vec![&node]).finalize();
+    assert_eq!(query, r"CREATE (n:Profile { name: 'x\'}) DELETE n //' })");
+}
+
+#[test]
+fn where_and_set_escape_values() {
+    let node: Entity = Node::new("n", "Profile", None, None).into();
+
+    let query = Query::init()
+        .r#match(&node, false)
+        .r#where("name", CompOper::Equal, PropType::str("x' OR 1=1 //"))
+        .set("name", PropType::str("O'Brien"))
+        .finalize();
+    assert_eq!(
+        query,
+        "MATCH (n:Profile) WHERE n.name = 'x\\' OR 1=1 //'\nSET n.name = 'O\\'Brien'"
+    );
+}
+
+#[test]
+fn props_keep_insertion_order() {
+    let mut props = Props::new();
+    for key in ["zeta", "alpha", "mid", "beta"] {
+        props.insert(key.to_string(), PropType::str(key));
+    }
+    props.insert(String::from("alpha"), PropType::int(1));
+
+    let node = Node::new("n", "Profile", Some(props), None);
+    let query = Query::init()
+        .create(vec![&node.into()])
+        .r#return("n")
+        .finalize();
+
+    assert_eq!(
+        query,
+        "CREATE (n:Profile { zeta: 'zeta',alpha: 1,mid: 'mid',beta: 'beta' })\nRETURN n"
+    );
+}
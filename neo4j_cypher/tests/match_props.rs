@@ -0,0 +1,93 @@
+use neo4j_cypher::entity::{Entity, Node, PropType, Props, Relation};
+use neo4j_cypher::query::match_query::CompOper;
+use neo4j_cypher::query::{PropsMatch, Query, QueryTrait};
+
+fn profile(nv: &str) -> Node<'static> {
+    let mut props = Props::new();
+    props.insert(String::from("name"), PropType::str("x"));
+    props.insert(String::from("age"), PropType::int(3));
+    props.insert(String::from("email"), PropType::Null);
+    Node::new(nv, "Profile", Some(props), None)
+}
+
+#[test]
+fn inline_props() {
+    let query = Query::init()
+        .match_props(
+            &profile("n").into(),
+            PropsMatch::Inline,
+            Some(vec!["name", "age"]),
+            false,
+        )
+        .r#return("n")
+        .finalize();
+
+    assert_eq!(query, "MATCH (n:Profile { name: 'x',age: 3 })\nRETURN n");
+}
+
+#[test]
+fn where_props() {
+    let query = Query::init()
+        .match_props(
+            &profile("n").into(),
+            PropsMatch::Where,
+            Some(vec!["age", "name"]),
+            true,
+        )
+        .and("age", CompOper::Less, PropType::int(10))
+        .r#return("n")
+        .finalize();
+
+    assert_eq!(
+        query,
+        "OPTIONAL MATCH (n:Profile) WHERE n.age = 3 AND n.name = 'x' AND n.age < 10\nRETURN n"
+    );
+}
+
+#[test]
+fn skips_null_and_unknown_fields() {
+    let query = Query::init()
+        .match_props(
+            &profile("n").into(),
+            PropsMatch::Where,
+            Some(vec!["email", "missing", "name"]),
+            false,
+        )
+        .r#return("n")
+        .finalize();
+
+    assert_eq!(query, "MATCH (n:Profile) WHERE n.name = 'x'\nRETURN n");
+
+    let query = Query::init()
+        .match_props(&profile("n").into(), PropsMatch::Inline, None, false)
+        .r#return("n")
+        .finalize();
+
+    assert_eq!(query, "MATCH (n:Profile { name: 'x',age: 3 })\nRETURN n");
+}
+
+#[test]
+fn relation_props() {
+    let mut props = Props::new();
+    props.insert(String::from("since"), PropType::int(2020));
+    let rel: Entity = Relation::new(profile("a"), profile("b"), "KNOWS", Some(props)).into();
+
+    let query = Query::init()
+        .match_props(&rel, PropsMatch::Inline, None, false)
+        .r#return("r")
+        .finalize();
+    assert_eq!(
+        query,
+        "MATCH (a:Profile)-[r:KNOWS { since: 2020 }]->(b:Profile)\nRETURN r"
+    );
+
+    let (query, params) = Query::init_with_params()
+        .match_props(&rel, PropsMatch::Where, None, false)
+        .r#return("r")
+        .finalize_with_params();
+    assert_eq!(
+        query,
+        "MATCH (a:Profile)-[r:KNOWS]->(b:Profile) WHERE r.since = $p0\nRETURN r"
+    );
+    assert_eq!(params.len(), 1);
+}
@@ -0,0 +1,274 @@
+use neo4j_cypher::entity::{Entity, KeyError, Node, PropType, Props, Relation};
+use neo4j_cypher::query::{Query, QueryTrait};
+
+fn props() -> Props {
+    Props::from([
+        (String::from("id"), PropType::int(1)),
+        (String::from("name"), PropType::str("a")),
+        (String::from("email"), PropType::Null),
+    ])
+}
+
+fn keyed() -> Entity<'static> {
+    Node::new("n", "Profile", Some(props()), None)
+        .with_keys(vec!["id"])
+        .into()
+}
+
+#[test]
+fn merge_without_keys() {
+    let node: Entity = Node::new("n", "Profile", Some(props()), None).into();
+    let query = Query::init().merge(&node).r#return("n").finalize();
+
+    assert_eq!(query, "MERGE (n:Profile { id: 1,name: 'a' })\nRETURN n");
+}
+
+#[test]
+fn merge_by_keys() {
+    let query = Query::init().merge(&keyed()).r#return("n").finalize();
+
+    assert_eq!(
+        query,
+        "MERGE (n:Profile { id: 1 })\n\
+         ON CREATE SET n.name = 'a'\n\
+         ON MATCH SET n.name = 'a'\n\
+         RETURN n"
+    );
+}
+
+#[test]
+fn merge_by_keys_only() {
+    let props = Props::from([(String::from("id"), PropType::int(1))]);
+    let node: Entity = Node::new("n", "Profile", Some(props), None)
+        .with_keys(vec!["id"])
+        .into();
+    let query = Query::init().merge(&node).finalize();
+
+    assert_eq!(query, "MERGE (n:Profile { id: 1 })");
+}
+
+#[test]
+fn null_or_missing_key() {
+    let null = Node::new("n", "Profile", Some(props()), None).try_with_keys(vec!["email"]);
+    let missing = Node::new("n", "Profile", Some(props()), None).try_with_keys(vec!["login"]);
+
+    assert_eq!(null.err(), Some(KeyError::Missing(String::from("email"))));
+    assert_eq!(
+        missing.err(),
+        Some(KeyError::Missing(String::from("login")))
+    );
+}
+
+#[test]
+#[should_panic(expected = "key property \"email\" is missing or Null")]
+fn null_key_panics() {
+    Node::new("n", "Profile", Some(props()), None).with_keys(vec!["email"]);
+}
+
+#[test]
+fn chained_on_create_and_on_match() {
+    let query = Query::init()
+        .merge(&keyed())
+        .on_create_set(Props::from([
+            (String::from("created"), PropType::Bool(true)),
+            (String::from("visits"), PropType::int(1)),
+        ]))
+        .on_match_set(Props::from([(String::from("visits"), PropType::int(2))]))
+        .on_create_set(Props::new())
+        .r#return("n")
+        .finalize();
+
+    assert_eq!(
+        query,
+        "MERGE (n:Profile { id: 1 })\n\
+         ON CREATE SET n.name = 'a'\n\
+         ON MATCH SET n.name = 'a'\n\
+         ON CREATE SET n.created = true, n.visits = 1\n\
+         ON MATCH SET n.visits = 2\n\
+         RETURN n"
+    );
+}
+
+#[test]
+fn merge_with_params() {
+    let (query, params) = Query::init_with_params()
+        .merge(&keyed())
+        .on_create_set(Props::from([(
+            String::from("created"),
+            PropType::Bool(true),
+        )]))
+        .finalize_with_params();
+
+    assert_eq!(
+        query,
+        "MERGE (n:Profile { id: $p0 })\n\
+         ON CREATE SET n.name = $p1\n\
+         ON MATCH SET n.name = $p2\n\
+         ON CREATE SET n.created = $p3"
+    );
+    assert_eq!(params.len(), 4);
+    assert_eq!(params["p3"], PropType::Bool(true));
+}
+
+#[test]
+fn merge_relation() {
+    let from = Node::new("a", "Profile", None, None);
+    let to = Node::new("b", "Profile", None, None);
+    let rel: Entity = Relation::new(from, to, "KNOWS", None).into();
+
+    let query = Query::init()
+        .merge(&rel)
+        .on_create_set(Props::from([(String::from("since"), PropType::int(2020))]))
+        .r#return("r")
+        .finalize();
+
+    assert_eq!(
+        query,
+        "MERGE (a)-[r:KNOWS]->(b)\nON CREATE SET r.since = 2020\nRETURN r"
+    );
+}
+
+#[cfg(feature = "derive")]
+mod derive {
+    mod account {
+        use neo4j_cypher::CypQue;
+        use std::fmt::Display;
+
+        #[derive(CypQue)]
+        pub struct Account {
+            #[cypher(key)]
+            pub login: String,
+            pub age: u8,
+            pub email: Option<String>,
+        }
+    }
+
+    mod contact {
+        use neo4j_cypher::CypQue;
+        use std::fmt::Display;
+
+        #[derive(CypQue)]
+        pub struct Contact {
+            #[cypher(key)]
+            pub email: Option<String>,
+            pub name: String,
+        }
+    }
+
+    mod session {
+        use neo4j_cypher::CypQue;
+        use std::fmt::Display;
+
+        #[derive(CypQue)]
+        pub struct Session {
+            #[cypher(key, rename = "sessionId")]
+            pub id: u32,
+            #[cypher(key)]
+            pub device: String,
+            pub active: bool,
+        }
+    }
+
+    use neo4j_cypher::entity::NodeTrait;
+    use neo4j_cypher::entity::{KeyError, PropType};
+    use neo4j_cypher::query::match_query::CompOper;
+    use neo4j_cypher::query::{Query, QueryTrait};
+
+    fn account() -> account::Account {
+        account::Account {
+            login: String::from("a"),
+            age: 30,
+            email: None,
+        }
+    }
+
+    fn session() -> session::Session {
+        session::Session {
+            id: 7,
+            device: String::from("phone"),
+            active: true,
+        }
+    }
+
+    #[test]
+    fn keys() {
+        assert_eq!(account().keys(), vec!["login"]);
+        assert_eq!(session().keys(), vec!["sessionId", "device"]);
+    }
+
+    #[test]
+    fn merge_node_by_key() {
+        let query = Query::init()
+            .merge(&account().merge_node("n").into())
+            .r#return("n")
+            .finalize();
+
+        assert_eq!(
+            query,
+            "MERGE (n:Account { login: 'a' })\n\
+             ON CREATE SET n.age = 30\n\
+             ON MATCH SET n.age = 30\n\
+             RETURN n"
+        );
+    }
+
+    #[test]
+    fn match_node_by_key() {
+        let query = Query::init()
+            .r#match(&account().match_node("n").into(), false)
+            .r#where("age", CompOper::More, PropType::int(18))
+            .set("age", PropType::int(31))
+            .finalize();
+
+        assert_eq!(
+            query,
+            "MATCH (n:Account { login: 'a' }) WHERE n.age > 18\nSET n.age = 31"
+        );
+    }
+
+    #[test]
+    fn renamed_keys() {
+        let merge = Query::init()
+            .merge(&session().merge_node("s").into())
+            .finalize();
+        let r#match = Query::init()
+            .r#match(&session().match_node("s").into(), false)
+            .r#where("active", CompOper::Equal, PropType::Bool(true))
+            .r#return("s")
+            .finalize();
+
+        assert_eq!(
+            merge,
+            "MERGE (s:Session { sessionId: 7,device: 'phone' })\n\
+             ON CREATE SET s.active = true\n\
+             ON MATCH SET s.active = true"
+        );
+        assert_eq!(
+            r#match,
+            "MATCH (s:Session { sessionId: 7,device: 'phone' }) WHERE s.active = true\nRETURN s"
+        );
+    }
+
+    #[test]
+    fn none_key() {
+        let contact = contact::Contact {
+            email: None,
+            name: String::from("x"),
+        };
+        let missing = Err(KeyError::Missing(String::from("email")));
+
+        assert_eq!(contact.try_merge_node("n").map(|_| ()), missing);
+        assert_eq!(contact.try_match_node("n").map(|_| ()), missing);
+    }
+
+    #[test]
+    #[should_panic(expected = "key property \"email\" is missing or Null")]
+    fn merge_none_key_panics() {
+        let contact = contact::Contact {
+            email: None,
+            name: String::from("x"),
+        };
+
+        contact.merge_node("n");
+    }
+}
@@ -0,0 +1,168 @@
+use neo4j_cypher::entity::{Point, PropType, Props};
+use neo4j_cypher::query::ast::NodePattern;
+use neo4j_cypher::query::match_query::CompOper;
+use neo4j_cypher::query::{Query, QueryTrait};
+
+fn address() -> Props {
+    Props::from([
+        (String::from("city"), PropType::str("O'Town")),
+        (String::from("zip"), PropType::int(12)),
+    ])
+}
+
+#[test]
+fn nested_maps() {
+    let mut props = Props::new();
+    props.insert(String::from("name"), PropType::str("x"));
+    props.insert(String::from("address"), PropType::Map(address()));
+    props.insert(
+        String::from("tags"),
+        PropType::Array(vec![PropType::Map(address())]),
+    );
+
+    assert_eq!(
+        PropType::Map(props).to_prop(),
+        "{name: 'x',address: {city: 'O\\'Town',zip: 12},tags: [{city: 'O\\'Town',zip: 12}]}"
+    );
+}
+
+#[test]
+fn set_props_extends_node() {
+    let query = Query::init()
+        .match_pattern(NodePattern::var("n").label("Profile").into(), false)
+        .r#where("name", CompOper::Equal, PropType::str("x"))
+        .set_props(address())
+        .r#return("n")
+        .finalize();
+
+    assert_eq!(
+        query,
+        "MATCH (n:Profile) WHERE n.name = 'x'\nSET n += {city: 'O\\'Town',zip: 12}\nRETURN n"
+    );
+
+    let (query, params) = Query::init_with_params()
+        .match_pattern(NodePattern::var("n").into(), false)
+        .r#where("name", CompOper::Equal, PropType::str("x"))
+        .set_props_var("n", address())
+        .finalize_with_params();
+
+    assert_eq!(query, "MATCH (n) WHERE n.name = $p0\nSET n += $p1");
+    assert_eq!(params["p1"], PropType::Map(address()));
+}
+
+#[test]
+fn json() {
+    let mut props = address();
+    props.insert(String::from("quote"), PropType::str("say \"hi\"\n"));
+    props.insert(String::from("score"), PropType::float(f64::NAN));
+    props.insert(
+        String::from("at"),
+        PropType::Point(Point::cartesian(1.0, 2.0)),
+    );
+    props.insert(String::from("on"), PropType::date("2024-05-01"));
+    props.insert(
+        String::from("list"),
+        PropType::Array(vec![PropType::Bool(true), PropType::Null]),
+    );
+
+    assert_eq!(
+        PropType::Map(props).to_json(),
+        r#"{"city":"O'Town","zip":12,"quote":"say \"hi\"\n","score":null,"at":{"x":1.0,"y":2.0,"crs":"cartesian"},"on":"2024-05-01","list":[true,null]}"#
+    );
+}
+
+#[cfg(feature = "derive")]
+mod derive {
+    mod address {
+        use neo4j_cypher::CypQue;
+        use std::fmt::Display;
+
+        #[derive(Debug, Clone, CypQue)]
+        pub struct Address {
+            pub city: String,
+            pub zip: Option<u32>,
+        }
+    }
+
+    mod account {
+        use neo4j_cypher::CypQue;
+        use std::fmt::Display;
+
+        use super::address::Address;
+
+        #[derive(Debug, Clone, CypQue)]
+        #[cypher(rename = "Profile")]
+        pub struct Account {
+            pub name: String,
+            #[cypher(flatten = "home_")]
+            pub home: Address,
+            #[cypher(map)]
+            pub work: Option<Address>,
+            #[cypher(json, rename = "meta")]
+            pub extra: Address,
+        }
+    }
+
+    use neo4j_cypher::entity::{NodeTrait, PropType, Props};
+    use neo4j_cypher::query::ast::NodePattern;
+    use neo4j_cypher::query::match_query::CompOper;
+    use neo4j_cypher::query::{Query, QueryTrait};
+
+    #[test]
+    fn nested_structs() {
+        let address = address::Address {
+            city: String::from("x"),
+            zip: None,
+        };
+        let account = account::Account {
+            name: String::from("a"),
+            home: address.clone(),
+            work: None,
+            extra: address,
+        };
+
+        let query = Query::init()
+            .create(vec![&account.node("n").into()])
+            .r#return("n")
+            .finalize();
+
+        assert_eq!(
+            query,
+            "CREATE (n:Profile { name: 'a',home_city: 'x',meta: '{\\\"city\\\":\\\"x\\\",\\\"zip\\\":null}' })\nRETURN n"
+        );
+    }
+
+    #[test]
+    fn map_field_is_set_with_plus_equal() {
+        let address = address::Address {
+            city: String::from("x"),
+            zip: Some(1),
+        };
+        let account = account::Account {
+            name: String::from("a"),
+            home: address.clone(),
+            work: Some(address.clone()),
+            extra: address,
+        };
+
+        let node = account.node("n");
+        let work = match node.props().as_ref().and_then(|props| props.get("work")) {
+            Some(PropType::Map(work)) => work.clone(),
+            other => panic!("expected a map, got {:?}", other),
+        };
+        let (query, params) = Query::init_with_params()
+            .match_pattern(NodePattern::var("n").label("Profile").into(), false)
+            .r#where("name", CompOper::Equal, PropType::str("a"))
+            .set_props(work)
+            .finalize_with_params();
+
+        assert_eq!(query, "MATCH (n:Profile) WHERE n.name = $p0\nSET n += $p1");
+        assert_eq!(
+            params["p1"],
+            PropType::Map(Props::from([
+                (String::from("city"), PropType::str("x")),
+                (String::from("zip"), PropType::int(1)),
+            ]))
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use neo4j_cypher::entity::{IntRangeError, PropType};
+
+fn from_type<T: std::fmt::Display + 'static>(tt: &str, value: T) -> PropType {
+    PropType::from_type(tt, Some(Box::new(value))).unwrap()
+}
+
+#[test]
+fn floats() {
+    let cases = [
+        (PropType::float(0.5), "0.5"),
+        (PropType::float(1.0), "1.0"),
+        (PropType::float(-2.25f32), "-2.25"),
+        (PropType::float(1e300), "1e300"),
+        (PropType::float(f64::NAN), "toFloat('NaN')"),
+        (PropType::float(f64::INFINITY), "toFloat('Infinity')"),
+        (PropType::float(f64::NEG_INFINITY), "toFloat('-Infinity')"),
+    ];
+
+    for (value, literal) in cases {
+        assert_eq!(value.to_prop(), literal);
+    }
+}
+
+#[test]
+fn numeric_types() {
+    assert_eq!(from_type("f64", 0.5f64), PropType::Float(0.5));
+    assert_eq!(from_type("f32", 0.1f32), PropType::Float(0.1));
+    assert_eq!(from_type("f32", 0.1f32).to_prop(), "0.1");
+    assert_eq!(from_type("i8", -3i8).to_prop(), "-3");
+    assert_eq!(
+        from_type("u64", u64::MAX / 2).to_prop(),
+        "9223372036854775807"
+    );
+    assert_eq!(
+        from_type("i128", i64::MIN as i128).to_prop(),
+        "-9223372036854775808"
+    );
+
+    let scores = PropType::arr("f64", vec![0.5, 2.0]).unwrap();
+    assert_eq!(scores.to_prop(), "[0.5,2.0]");
+}
+
+#[test]
+fn integer_range() {
+    assert_eq!(PropType::try_int(42u128), Ok(PropType::int(42)));
+    assert_eq!(
+        PropType::try_int(u64::MAX),
+        Err(IntRangeError(u64::MAX.to_string()))
+    );
+    assert!(PropType::try_int(i128::MIN).is_err());
+}
+
+#[test]
+fn integer_overflow_is_an_error() {
+    assert_eq!(
+        PropType::from_type("u128", Some(Box::new(u128::MAX))),
+        Err(IntRangeError(u128::MAX.to_string()))
+    );
+    assert!(PropType::from_type("usize", Some(Box::new(usize::MAX))).is_err());
+    assert!(PropType::arr("u64", vec![1, u64::MAX]).is_err());
+}
+
+#[cfg(feature = "derive")]
+mod derive {
+    mod counter {
+        use neo4j_cypher::CypQue;
+        use std::fmt::Display;
+
+        #[derive(CypQue)]
+        pub struct Counter {
+            pub hits: u64,
+            pub history: Option<Vec<u128>>,
+        }
+    }
+
+    use neo4j_cypher::entity::{IntRangeError, NodeTrait, PropType};
+
+    #[test]
+    fn try_node() {
+        let small = counter::Counter {
+            hits: 3,
+            history: Some(vec![1, 2]),
+        };
+        let node = small.try_node("n").unwrap();
+        assert_eq!(node.props().as_ref().unwrap()["hits"], PropType::int(3));
+
+        let big = counter::Counter {
+            hits: 3,
+            history: Some(vec![u128::MAX]),
+        };
+        assert_eq!(
+            big.try_node("n").err(),
+            Some(IntRangeError(u128::MAX.to_string()))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit into a 64-bit Neo4j Integer")]
+    fn node_panics_on_overflow() {
+        let counter = counter::Counter {
+            hits: u64::MAX,
+            history: None,
+        };
+        counter.node("n");
+    }
+}
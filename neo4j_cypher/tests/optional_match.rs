@@ -0,0 +1,89 @@
+use neo4j_cypher::entity::{Entity, Node, PropType, Props, Relation};
+use neo4j_cypher::query::ast::Expr;
+use neo4j_cypher::query::match_query::CompOper;
+use neo4j_cypher::query::{Query, QueryTrait};
+
+fn node(nv: &str) -> Node<'static> {
+    Node::new(nv, "Profile", None, None)
+}
+
+fn keyed(nv: &str) -> Node<'static> {
+    let props = Props::from([(String::from("id"), PropType::int(1))]);
+    Node::new(nv, "Profile", Some(props), None).with_keys(vec!["id"])
+}
+
+fn knows() -> Entity<'static> {
+    Relation::new(node("a"), node("b"), "KNOWS", None).into()
+}
+
+#[test]
+fn node_match() {
+    for (optional, prefix) in [(false, ""), (true, "OPTIONAL ")] {
+        let query = Query::init()
+            .r#match(&node("n").into(), optional)
+            .r#where("age", CompOper::More, PropType::int(18))
+            .r#return("n")
+            .finalize();
+
+        assert_eq!(
+            query,
+            format!("{}MATCH (n:Profile) WHERE n.age > 18\nRETURN n", prefix)
+        );
+    }
+}
+
+#[test]
+fn keyed_node_match() {
+    for (optional, prefix) in [(false, ""), (true, "OPTIONAL ")] {
+        let query = Query::init()
+            .r#match(&keyed("n").into(), optional)
+            .r#where("name", CompOper::IsNull, PropType::Null)
+            .r#return("n")
+            .finalize();
+
+        assert_eq!(
+            query,
+            format!(
+                "{}MATCH (n:Profile {{ id: 1 }}) WHERE n.name IS NULL\nRETURN n",
+                prefix
+            )
+        );
+    }
+}
+
+#[test]
+fn relation_match() {
+    for (optional, prefix) in [(false, ""), (true, "OPTIONAL ")] {
+        let query = Query::init()
+            .r#match(&knows(), optional)
+            .where_expr(Expr::cond("a", "age", CompOper::More, PropType::int(18)))
+            .return_many(vec!["a", "b"])
+            .finalize();
+
+        assert_eq!(
+            query,
+            format!(
+                "{}MATCH (a:Profile)-[r:KNOWS]->(b:Profile) WHERE a.age > 18\nRETURN a,b",
+                prefix
+            )
+        );
+    }
+}
+
+#[test]
+fn optional_after_required_match() {
+    let query = Query::init()
+        .r#match(&node("a").into(), false)
+        .r#where("age", CompOper::More, PropType::int(18))
+        .r#match(&knows(), true)
+        .r#where_var("b", "age", CompOper::Less, PropType::int(30))
+        .return_many(vec!["a", "b"])
+        .finalize();
+
+    assert_eq!(
+        query,
+        "MATCH (a:Profile) WHERE a.age > 18\n\
+         OPTIONAL MATCH (a:Profile)-[r:KNOWS]->(b:Profile) WHERE b.age < 30\n\
+         RETURN a,b"
+    );
+}
@@ -0,0 +1,113 @@
+use neo4j_cypher::entity::{Node, PropType, Props};
+use neo4j_cypher::query::ast::Expr;
+use neo4j_cypher::query::match_query::CompOper;
+use neo4j_cypher::query::{Query, QueryTrait};
+
+#[test]
+fn str_arr_is_a_list() {
+    assert_eq!(
+        PropType::str_arr(Some(" [ 'Bob', \"O\\'Tom\", 3, -1.5, true, null, [] ] ")),
+        PropType::Array(vec![
+            PropType::str("Bob"),
+            PropType::str("O'Tom"),
+            PropType::int(3),
+            PropType::float(-1.5),
+            PropType::Bool(true),
+            PropType::Null,
+            PropType::Array(Vec::new()),
+        ])
+    );
+    assert_eq!(PropType::str_arr(None), PropType::Null);
+}
+
+#[test]
+#[should_panic(expected = "\"['Bob'\" is not a list literal")]
+fn str_arr_rejects_malformed_text() {
+    PropType::str_arr(Some("['Bob'"));
+}
+
+#[test]
+fn str_arr_is_a_single_list_parameter() {
+    let mut props = Props::new();
+    props.insert(
+        String::from("friends"),
+        PropType::str_arr(Some("['Bob', 'Tom']")),
+    );
+    let node = Node::new("n", "Profile", Some(props), None);
+
+    let (query, params) = Query::init_with_params()
+        .create(vec![&node.into()])
+        .finalize_with_params();
+
+    assert_eq!(query, "CREATE (n:Profile { friends: $p0 })");
+    assert_eq!(
+        params["p0"],
+        PropType::Array(vec![PropType::str("Bob"), PropType::str("Tom")])
+    );
+    assert_eq!(params["p0"].to_json(), "[\"Bob\",\"Tom\"]");
+}
+
+#[cfg(feature = "derive")]
+mod derive {
+    mod account {
+        use neo4j_cypher::CypQue;
+        use std::fmt::Display;
+
+        #[derive(CypQue)]
+        pub struct Account {
+            pub name: String,
+            #[cypher(default = "['Bob', 'Tom']")]
+            pub friends: Option<Vec<String>>,
+        }
+    }
+
+    use neo4j_cypher::entity::{NodeTrait, PropType};
+    use neo4j_cypher::query::{Query, QueryTrait};
+
+    #[test]
+    fn default_list_is_a_parameter() {
+        let account = account::Account {
+            name: String::from("a"),
+            friends: None,
+        };
+
+        let (query, params) = Query::init_with_params()
+            .create(vec![&account.node("n").into()])
+            .finalize_with_params();
+
+        assert_eq!(query, "CREATE (n:Account { name: $p0,friends: $p1 })");
+        assert_eq!(
+            params["p1"],
+            PropType::Array(vec![PropType::str("Bob"), PropType::str("Tom")])
+        );
+    }
+}
+
+#[test]
+fn named_params_do_not_collide_with_positional_ones() {
+    let named = |name: &str, key: &str, value: PropType| {
+        Expr::Compare(
+            Box::new(Expr::prop("n", key)),
+            CompOper::Equal,
+            Box::new(Expr::Param(String::from(name), value)),
+        )
+    };
+
+    let (query, params) = Query::init_with_params()
+        .r#match(&Node::new("n", "Profile", None, None).into(), false)
+        .where_expr(named("p0", "id", PropType::int(7)))
+        .and("age", CompOper::More, PropType::int(18))
+        .and_expr(named("p1", "name", PropType::str("a")))
+        .and("active", CompOper::Equal, PropType::Bool(true))
+        .finalize_with_params();
+
+    assert_eq!(
+        query,
+        "MATCH (n:Profile) WHERE n.id = $p0 AND n.age > $p1 AND n.name = $p11 AND n.active = $p2"
+    );
+    assert_eq!(params.len(), 4);
+    assert_eq!(params["p0"], PropType::int(7));
+    assert_eq!(params["p1"], PropType::int(18));
+    assert_eq!(params["p11"], PropType::str("a"));
+    assert_eq!(params["p2"], PropType::Bool(true));
+}
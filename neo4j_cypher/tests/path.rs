@@ -0,0 +1,249 @@
+use neo4j_cypher::entity::{Node, PropType};
+use neo4j_cypher::query::ast::{
+    Expr, NodePattern, PathPattern, QuantifiedPath, Quantifier, RelPattern,
+};
+use neo4j_cypher::query::match_query::CompOper;
+use neo4j_cypher::query::{Query, QueryTrait};
+
+fn rendered(rel: RelPattern) -> String {
+    let path = PathPattern::new(NodePattern::var("a")).hop(rel, NodePattern::var("b"));
+
+    Query::init()
+        .match_pattern(path.into(), false)
+        .r#where("id", CompOper::Equal, PropType::int(1))
+        .r#return("b")
+        .finalize()
+        .lines()
+        .next()
+        .unwrap()
+        .trim_start_matches("MATCH ")
+        .trim_end_matches(" WHERE a.id = 1")
+        .to_string()
+}
+
+#[test]
+fn relationship_forms() {
+    let cases = [
+        (RelPattern::outgoing(), "(a)-[]->(b)"),
+        (RelPattern::incoming().var("r"), "(a)<-[r]-(b)"),
+        (RelPattern::undirected().r#type("KNOWS"), "(a)-[:KNOWS]-(b)"),
+        (
+            RelPattern::outgoing()
+                .var("r")
+                .r#type("KNOWS")
+                .r#type("LIKES"),
+            "(a)-[r:KNOWS|LIKES]->(b)",
+        ),
+        (
+            RelPattern::outgoing()
+                .r#type("KNOWS")
+                .prop("since", PropType::int(2020)),
+            "(a)-[:KNOWS { since: 2020 }]->(b)",
+        ),
+        (RelPattern::outgoing().any_length(), "(a)-[*]->(b)"),
+        (
+            RelPattern::outgoing().length(Some(2), Some(2)),
+            "(a)-[*2]->(b)",
+        ),
+        (
+            RelPattern::outgoing()
+                .r#type("KNOWS")
+                .length(Some(1), Some(3)),
+            "(a)-[:KNOWS*1..3]->(b)",
+        ),
+        (
+            RelPattern::outgoing().length(None, Some(3)),
+            "(a)-[*..3]->(b)",
+        ),
+        (
+            RelPattern::outgoing().length(Some(2), None),
+            "(a)-[*2..]->(b)",
+        ),
+    ];
+
+    for (rel, pattern) in cases {
+        assert_eq!(rendered(rel), pattern);
+    }
+}
+
+#[test]
+fn multi_hop_chain() {
+    let owner = Node::new("c", "Profile", None, None);
+    let path = PathPattern::new(
+        NodePattern::var("a")
+            .label("Profile")
+            .prop("name", PropType::str("admin")),
+    )
+    .hop(
+        RelPattern::outgoing().var("k").r#type("KNOWS"),
+        NodePattern::var("b").label("Profile"),
+    )
+    .hop(RelPattern::incoming().r#type("OWNS"), (&owner).into())
+    .hop(RelPattern::undirected(), NodePattern::default());
+
+    let query = Query::init()
+        .match_pattern(path.into(), false)
+        .where_var("b", "age", CompOper::More, PropType::int(18))
+        .return_many(vec!["a", "b", "c"])
+        .finalize();
+
+    assert_eq!(
+        query,
+        "MATCH (a:Profile { name: 'admin' })-[k:KNOWS]->(b:Profile)<-[:OWNS]-(c:Profile)-[]-() WHERE b.age > 18\n\
+         RETURN a,b,c"
+    );
+}
+
+#[test]
+fn optional_path_match() {
+    let path = PathPattern::new(NodePattern::var("a")).hop(
+        RelPattern::outgoing().r#type("KNOWS").any_length(),
+        NodePattern::var("b"),
+    );
+
+    for (optional, prefix) in [(false, ""), (true, "OPTIONAL ")] {
+        let query = Query::init()
+            .match_pattern(path.clone().into(), optional)
+            .r#where("name", CompOper::Equal, PropType::str("admin"))
+            .r#return("b")
+            .finalize();
+
+        assert_eq!(
+            query,
+            format!(
+                "{}MATCH (a)-[:KNOWS*]->(b) WHERE a.name = 'admin'\nRETURN b",
+                prefix
+            )
+        );
+    }
+}
+
+#[test]
+fn named_shortest_paths() {
+    let path = || {
+        PathPattern::new(NodePattern::var("a").label("Profile"))
+            .hop(
+                RelPattern::outgoing().any_length(),
+                NodePattern::var("b").label("Profile"),
+            )
+            .named("p")
+    };
+    let cases = [
+        (path(), "p = (a:Profile)-[*]->(b:Profile)"),
+        (
+            path().shortest(),
+            "p = shortestPath((a:Profile)-[*]->(b:Profile))",
+        ),
+        (
+            path().all_shortest(),
+            "p = allShortestPaths((a:Profile)-[*]->(b:Profile))",
+        ),
+    ];
+
+    for (path, pattern) in cases {
+        let query = Query::init()
+            .match_pattern(path.into(), false)
+            .r#where("name", CompOper::Equal, PropType::str("admin"))
+            .return_items(vec![
+                Expr::nodes(Expr::var("p")).r#as("nodes"),
+                Expr::relationships(Expr::var("p")).r#as("rels"),
+                Expr::length(Expr::var("p")).into(),
+            ])
+            .finalize();
+
+        assert_eq!(
+            query,
+            format!(
+                "MATCH {} WHERE a.name = 'admin'\n\
+                 RETURN nodes(p) AS nodes,relationships(p) AS rels,length(p)",
+                pattern
+            )
+        );
+    }
+}
+
+#[test]
+fn quantified_path_patterns() {
+    let group = |quantifier| {
+        QuantifiedPath::new(
+            PathPattern::new(NodePattern::var("x"))
+                .hop(RelPattern::outgoing().r#type("NEXT"), NodePattern::var("y")),
+            quantifier,
+        )
+    };
+    let cases = [
+        (group(Quantifier::Plus), "((x)-[:NEXT]->(y))+"),
+        (group(Quantifier::Star), "((x)-[:NEXT]->(y))*"),
+        (
+            group(Quantifier::Range {
+                min: Some(1),
+                max: Some(5),
+            }),
+            "((x)-[:NEXT]->(y)){1,5}",
+        ),
+        (
+            group(Quantifier::Range {
+                min: Some(2),
+                max: Some(2),
+            }),
+            "((x)-[:NEXT]->(y)){2}",
+        ),
+        (
+            group(Quantifier::Range {
+                min: None,
+                max: Some(3),
+            }),
+            "((x)-[:NEXT]->(y)){,3}",
+        ),
+        (
+            group(Quantifier::Range {
+                min: None,
+                max: None,
+            }),
+            "((x)-[:NEXT]->(y))*",
+        ),
+        (
+            group(Quantifier::Range {
+                min: Some(1),
+                max: None,
+            })
+            .r#where(Expr::cond("x", "weight", CompOper::Less, PropType::int(10)))
+            .r#where(Expr::cond(
+                "y",
+                "active",
+                CompOper::Equal,
+                PropType::Bool(true),
+            )),
+            "((x)-[:NEXT]->(y) WHERE x.weight < 10 AND y.active = true){1,}",
+        ),
+    ];
+
+    for (group, pattern) in cases {
+        let path = PathPattern::new(NodePattern::var("a").label("Stop"))
+            .repeat(group, NodePattern::var("b").label("Stop"));
+        let query = Query::init()
+            .match_pattern(path.into(), false)
+            .r#where("name", CompOper::Equal, PropType::str("start"))
+            .r#return("b")
+            .finalize();
+
+        assert_eq!(
+            query,
+            format!(
+                "MATCH (a:Stop) {} (b:Stop) WHERE a.name = 'start'\nRETURN b",
+                pattern
+            )
+        );
+    }
+}
+
+#[test]
+#[should_panic(expected = "the first node of a MATCH pattern must have a variable")]
+fn pattern_without_start_variable() {
+    Query::init().match_pattern(
+        PathPattern::new(NodePattern::default().label("Profile"))
+            .hop(RelPattern::outgoing(), NodePattern::var("b"))
+            .into(),
+        false,
+    );
+}
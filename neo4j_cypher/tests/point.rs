@@ -0,0 +1,226 @@
+use neo4j_cypher::entity::{Point, PropType};
+use neo4j_cypher::query::ast::{Expr, NodePattern};
+use neo4j_cypher::query::match_query::CompOper;
+use neo4j_cypher::query::{Query, QueryTrait};
+
+#[test]
+fn point_literals() {
+    let cases = [
+        (
+            Point::cartesian(1.0, 2.5),
+            "point({x: 1.0, y: 2.5, crs: 'cartesian'})",
+        ),
+        (
+            Point::cartesian_3d(1.0, 2.0, -3.0),
+            "point({x: 1.0, y: 2.0, z: -3.0, crs: 'cartesian-3d'})",
+        ),
+        (
+            Point::wgs84(12.5, 55.6),
+            "point({x: 12.5, y: 55.6, crs: 'wgs-84'})",
+        ),
+        (
+            Point::wgs84_3d(12.5, 55.6, 100.0),
+            "point({x: 12.5, y: 55.6, z: 100.0, crs: 'wgs-84-3d'})",
+        ),
+    ];
+
+    for (point, literal) in cases {
+        assert_eq!(PropType::Point(point).to_prop(), literal);
+    }
+}
+
+#[test]
+fn spatial_predicates() {
+    let query = Query::init()
+        .match_pattern(NodePattern::var("n").label("Place").into(), false)
+        .where_expr(Expr::within_bbox(
+            Expr::prop("n", "location"),
+            Expr::Value(Point::wgs84(10.0, 50.0).into()),
+            Expr::Value(Point::wgs84(20.0, 60.0).into()),
+        ))
+        .and_expr(
+            Expr::distance(
+                Expr::prop("n", "location"),
+                Expr::Value(Point::wgs84(12.5, 55.6).into()),
+            )
+            .compare(CompOper::LessEqual, PropType::float(1000.0)),
+        )
+        .r#return("n")
+        .finalize();
+
+    assert_eq!(
+        query,
+        "MATCH (n:Place) WHERE point.withinBBox(n.location, \
+         point({x: 10.0, y: 50.0, crs: 'wgs-84'}), point({x: 20.0, y: 60.0, crs: 'wgs-84'})) \
+         AND point.distance(n.location, point({x: 12.5, y: 55.6, crs: 'wgs-84'})) <= 1000.0\nRETURN n"
+    );
+}
+
+#[test]
+fn point_params() {
+    let (query, params) = Query::init_with_params()
+        .match_pattern(NodePattern::var("n").label("Place").into(), false)
+        .where_expr(Expr::within_bbox(
+            Expr::prop("n", "location"),
+            Expr::Value(Point::cartesian(0.0, 0.0).into()),
+            Expr::Value(Point::cartesian(1.0, 1.0).into()),
+        ))
+        .r#return("n")
+        .finalize_with_params();
+
+    assert_eq!(
+        query,
+        "MATCH (n:Place) WHERE point.withinBBox(n.location, $p0, $p1)\nRETURN n"
+    );
+    assert_eq!(params["p1"], PropType::Point(Point::cartesian(1.0, 1.0)));
+}
+
+#[cfg(feature = "templates")]
+mod templates {
+    use neo4j_cypher::entity::{Entity, Node, Point, PropType};
+    use neo4j_cypher::query::{Query, QueryTrait};
+
+    fn place() -> Entity<'static> {
+        Node::new("n", "Place", None, None).into()
+    }
+
+    #[test]
+    fn within_distance() {
+        let query = Query::init()
+            .r#match(&place(), false)
+            .where_within_distance("location", Point::cartesian(0.0, 0.0), 10.0)
+            .and_within_distance("home", Point::cartesian(1.0, 1.0), 2.5)
+            .or_within_distance_var("m", "location", Point::cartesian(2.0, 2.0), 5.0)
+            .and_within_distance_var("m", "home", Point::cartesian(3.0, 3.0), 1.0)
+            .or_within_distance("work", Point::cartesian(4.0, 4.0), 0.5)
+            .r#return("n")
+            .finalize();
+
+        assert_eq!(
+            query,
+            "MATCH (n:Place) WHERE \
+             point.distance(n.location, point({x: 0.0, y: 0.0, crs: 'cartesian'})) <= 10.0 \
+             AND point.distance(n.home, point({x: 1.0, y: 1.0, crs: 'cartesian'})) <= 2.5 \
+             OR point.distance(m.location, point({x: 2.0, y: 2.0, crs: 'cartesian'})) <= 5.0 \
+             AND point.distance(m.home, point({x: 3.0, y: 3.0, crs: 'cartesian'})) <= 1.0 \
+             OR point.distance(n.work, point({x: 4.0, y: 4.0, crs: 'cartesian'})) <= 0.5\n\
+             RETURN n"
+        );
+        assert_eq!(
+            Query::init()
+                .r#match(&place(), false)
+                .where_within_distance_var("m", "location", Point::wgs84(12.5, 55.6), 1000.0)
+                .finalize(),
+            "MATCH (n:Place) WHERE \
+             point.distance(m.location, point({x: 12.5, y: 55.6, crs: 'wgs-84'})) <= 1000.0"
+        );
+    }
+
+    #[test]
+    fn within_distance_with_params() {
+        let (query, params) = Query::init_with_params()
+            .r#match(&place(), false)
+            .where_within_distance("location", Point::wgs84(12.5, 55.6), 1000.0)
+            .or_within_distance_var("m", "location", Point::wgs84(10.0, 50.0), 50.0)
+            .finalize_with_params();
+
+        assert_eq!(
+            query,
+            "MATCH (n:Place) WHERE point.distance(n.location, $p0) <= $p1 \
+             OR point.distance(m.location, $p2) <= $p3"
+        );
+        assert_eq!(params["p0"], PropType::Point(Point::wgs84(12.5, 55.6)));
+        assert_eq!(params["p1"], PropType::float(1000.0));
+        assert_eq!(params["p2"], PropType::Point(Point::wgs84(10.0, 50.0)));
+        assert_eq!(params["p3"], PropType::float(50.0));
+    }
+
+    #[test]
+    fn within_bbox() {
+        let query = Query::init()
+            .r#match(&place(), false)
+            .where_within_bbox(
+                "location",
+                Point::cartesian(0.0, 0.0),
+                Point::cartesian(1.0, 1.0),
+            )
+            .and_within_bbox_var(
+                "m",
+                "location",
+                Point::cartesian(2.0, 2.0),
+                Point::cartesian(3.0, 3.0),
+            )
+            .or_within_bbox(
+                "home",
+                Point::cartesian(4.0, 4.0),
+                Point::cartesian(5.0, 5.0),
+            )
+            .and_within_bbox(
+                "work",
+                Point::cartesian(6.0, 6.0),
+                Point::cartesian(7.0, 7.0),
+            )
+            .or_within_bbox_var(
+                "m",
+                "home",
+                Point::cartesian(8.0, 8.0),
+                Point::cartesian(9.0, 9.0),
+            )
+            .finalize();
+
+        assert_eq!(
+            query,
+            "MATCH (n:Place) WHERE point.withinBBox(n.location, \
+             point({x: 0.0, y: 0.0, crs: 'cartesian'}), point({x: 1.0, y: 1.0, crs: 'cartesian'})) \
+             AND point.withinBBox(m.location, \
+             point({x: 2.0, y: 2.0, crs: 'cartesian'}), point({x: 3.0, y: 3.0, crs: 'cartesian'})) \
+             OR point.withinBBox(n.home, \
+             point({x: 4.0, y: 4.0, crs: 'cartesian'}), point({x: 5.0, y: 5.0, crs: 'cartesian'})) \
+             AND point.withinBBox(n.work, \
+             point({x: 6.0, y: 6.0, crs: 'cartesian'}), point({x: 7.0, y: 7.0, crs: 'cartesian'})) \
+             OR point.withinBBox(m.home, \
+             point({x: 8.0, y: 8.0, crs: 'cartesian'}), point({x: 9.0, y: 9.0, crs: 'cartesian'}))"
+        );
+        assert_eq!(
+            Query::init()
+                .r#match(&place(), false)
+                .where_within_bbox_var(
+                    "m",
+                    "location",
+                    Point::wgs84(10.0, 50.0),
+                    Point::wgs84(20.0, 60.0),
+                )
+                .finalize(),
+            "MATCH (n:Place) WHERE point.withinBBox(m.location, \
+             point({x: 10.0, y: 50.0, crs: 'wgs-84'}), point({x: 20.0, y: 60.0, crs: 'wgs-84'}))"
+        );
+    }
+
+    #[test]
+    fn within_bbox_with_params() {
+        let (query, params) = Query::init_with_params()
+            .r#match(&place(), false)
+            .where_within_bbox(
+                "location",
+                Point::wgs84(10.0, 50.0),
+                Point::wgs84(20.0, 60.0),
+            )
+            .and_within_bbox_var(
+                "m",
+                "location",
+                Point::cartesian(0.0, 0.0),
+                Point::cartesian(1.0, 1.0),
+            )
+            .finalize_with_params();
+
+        assert_eq!(
+            query,
+            "MATCH (n:Place) WHERE point.withinBBox(n.location, $p0, $p1) \
+             AND point.withinBBox(m.location, $p2, $p3)"
+        );
+        assert_eq!(params["p0"], PropType::Point(Point::wgs84(10.0, 50.0)));
+        assert_eq!(params["p1"], PropType::Point(Point::wgs84(20.0, 60.0)));
+        assert_eq!(params["p2"], PropType::Point(Point::cartesian(0.0, 0.0)));
+        assert_eq!(params["p3"], PropType::Point(Point::cartesian(1.0, 1.0)));
+    }
+}
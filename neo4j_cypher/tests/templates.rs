@@ -0,0 +1,252 @@
+#![cfg(feature = "templates")]
+
+use neo4j_cypher::entity::{Entity, Node, PropType};
+use neo4j_cypher::query::match_query::{CompOper, MatchTrait, OperandError};
+use neo4j_cypher::query::{Query, QueryTrait};
+
+fn profile() -> Entity<'static> {
+    Node::new("n", "Profile", None, None).into()
+}
+
+fn matched() -> Box<dyn MatchTrait> {
+    Query::init().r#match(&profile(), false)
+}
+
+fn matched_with_params() -> Box<dyn MatchTrait> {
+    Query::init_with_params().r#match(&profile(), false)
+}
+
+#[test]
+fn not_equal() {
+    let query = matched()
+        .where_ne_str("name", "a")
+        .and_ne_int("age", 1)
+        .or_ne_bool("active", true)
+        .and_ne_str_var("m", "name", "b")
+        .or_ne_int_var("m", "age", 2)
+        .and_ne_bool_var("m", "active", false)
+        .finalize();
+
+    assert_eq!(
+        query,
+        "MATCH (n:Profile) WHERE n.name <> 'a' AND n.age <> 1 OR n.active <> true \
+         AND m.name <> 'b' OR m.age <> 2 AND m.active <> false"
+    );
+    assert_eq!(
+        matched().where_ne_int_var("m", "age", 3).finalize(),
+        "MATCH (n:Profile) WHERE m.age <> 3"
+    );
+    assert_eq!(
+        matched().where_ne_bool_var("m", "active", true).finalize(),
+        "MATCH (n:Profile) WHERE m.active <> true"
+    );
+    assert_eq!(
+        matched()
+            .where_ne_str_var("m", "name", "c")
+            .or_ne_str("name", "d")
+            .and_ne_int_var("m", "age", 4)
+            .or_ne_bool_var("m", "active", false)
+            .and_ne_bool("active", true)
+            .or_ne_int("age", 5)
+            .finalize(),
+        "MATCH (n:Profile) WHERE m.name <> 'c' OR n.name <> 'd' AND m.age <> 4 \
+         OR m.active <> false AND n.active <> true OR n.age <> 5"
+    );
+    assert_eq!(
+        matched()
+            .where_ne_bool("active", false)
+            .or_ne_str_var("m", "name", "e")
+            .finalize(),
+        "MATCH (n:Profile) WHERE n.active <> false OR m.name <> 'e'"
+    );
+}
+
+#[test]
+fn not_equal_with_params() {
+    let (query, params) = matched_with_params()
+        .where_ne_str("name", "a")
+        .and_ne_int("age", 1)
+        .or_ne_bool_var("m", "active", true)
+        .finalize_with_params();
+
+    assert_eq!(
+        query,
+        "MATCH (n:Profile) WHERE n.name <> $p0 AND n.age <> $p1 OR m.active <> $p2"
+    );
+    assert_eq!(params["p0"], PropType::str("a"));
+    assert_eq!(params["p1"], PropType::int(1));
+    assert_eq!(params["p2"], PropType::Bool(true));
+}
+
+#[test]
+fn in_list() {
+    assert_eq!(
+        matched()
+            .where_in_str("name", vec!["a", "b"])
+            .and_in_int("age", vec![1, 2])
+            .or_in_str_var("m", "name", vec!["c"])
+            .and_in_int_var("m", "age", Vec::new())
+            .finalize(),
+        "MATCH (n:Profile) WHERE n.name IN ['a','b'] AND n.age IN [1,2] \
+         OR m.name IN ['c'] AND m.age IN []"
+    );
+    assert_eq!(
+        matched()
+            .where_in_int("age", vec![3])
+            .or_in_int("age", vec![4])
+            .and_in_str("name", vec!["d"])
+            .or_in_int_var("m", "age", vec![5])
+            .and_in_str_var("m", "name", vec!["e"])
+            .finalize(),
+        "MATCH (n:Profile) WHERE n.age IN [3] OR n.age IN [4] AND n.name IN ['d'] \
+         OR m.age IN [5] AND m.name IN ['e']"
+    );
+    assert_eq!(
+        matched()
+            .where_in_str_var("m", "name", vec!["f"])
+            .or_in_str("name", vec!["g"])
+            .finalize(),
+        "MATCH (n:Profile) WHERE m.name IN ['f'] OR n.name IN ['g']"
+    );
+    assert_eq!(
+        matched()
+            .where_in_int_var("m", "age", vec![6, 7])
+            .finalize(),
+        "MATCH (n:Profile) WHERE m.age IN [6,7]"
+    );
+}
+
+#[test]
+fn in_list_with_params() {
+    let (query, params) = matched_with_params()
+        .where_in_str("name", vec!["a", "b"])
+        .or_in_int_var("m", "age", vec![1])
+        .finalize_with_params();
+
+    assert_eq!(
+        query,
+        "MATCH (n:Profile) WHERE n.name IN $p0 OR m.age IN $p1"
+    );
+    assert_eq!(
+        params["p0"],
+        PropType::Array(vec![PropType::str("a"), PropType::str("b")])
+    );
+    assert_eq!(params["p1"], PropType::Array(vec![PropType::int(1)]));
+}
+
+#[test]
+fn string_matching() {
+    assert_eq!(
+        matched()
+            .where_starts_with("name", "a")
+            .and_ends_with("name", "b")
+            .or_contains("name", "c")
+            .and_regex("name", "d.*")
+            .finalize(),
+        "MATCH (n:Profile) WHERE n.name STARTS WITH 'a' AND n.name ENDS WITH 'b' \
+         OR n.name CONTAINS 'c' AND n.name =~ 'd.*'"
+    );
+    assert_eq!(
+        matched()
+            .where_ends_with("name", "a")
+            .or_starts_with("name", "b")
+            .and_contains("name", "c")
+            .or_regex("name", "d")
+            .finalize(),
+        "MATCH (n:Profile) WHERE n.name ENDS WITH 'a' OR n.name STARTS WITH 'b' \
+         AND n.name CONTAINS 'c' OR n.name =~ 'd'"
+    );
+    assert_eq!(
+        matched()
+            .where_contains("name", "a")
+            .or_ends_with("name", "b")
+            .and_starts_with("name", "c")
+            .finalize(),
+        "MATCH (n:Profile) WHERE n.name CONTAINS 'a' OR n.name ENDS WITH 'b' \
+         AND n.name STARTS WITH 'c'"
+    );
+    assert_eq!(
+        matched().where_regex("name", "(?i)a").finalize(),
+        "MATCH (n:Profile) WHERE n.name =~ '(?i)a'"
+    );
+}
+
+#[test]
+fn string_matching_with_var() {
+    assert_eq!(
+        matched()
+            .where_starts_with_var("m", "name", "a")
+            .and_ends_with_var("m", "name", "b")
+            .or_contains_var("m", "name", "c")
+            .and_regex_var("m", "name", "d")
+            .or_starts_with_var("m", "name", "e")
+            .and_contains_var("m", "name", "f")
+            .or_ends_with_var("m", "name", "g")
+            .or_regex_var("m", "name", "h")
+            .finalize(),
+        "MATCH (n:Profile) WHERE m.name STARTS WITH 'a' AND m.name ENDS WITH 'b' \
+         OR m.name CONTAINS 'c' AND m.name =~ 'd' OR m.name STARTS WITH 'e' \
+         AND m.name CONTAINS 'f' OR m.name ENDS WITH 'g' OR m.name =~ 'h'"
+    );
+    assert_eq!(
+        matched().where_ends_with_var("m", "name", "a").finalize(),
+        "MATCH (n:Profile) WHERE m.name ENDS WITH 'a'"
+    );
+    assert_eq!(
+        matched().where_contains_var("m", "name", "a").finalize(),
+        "MATCH (n:Profile) WHERE m.name CONTAINS 'a'"
+    );
+    assert_eq!(
+        matched().where_regex_var("m", "name", "a").finalize(),
+        "MATCH (n:Profile) WHERE m.name =~ 'a'"
+    );
+}
+
+#[test]
+fn string_matching_with_params() {
+    let (query, params) = matched_with_params()
+        .where_starts_with("name", "a")
+        .and_ends_with_var("m", "name", "b")
+        .or_contains("name", "c")
+        .and_regex("name", "d")
+        .finalize_with_params();
+
+    assert_eq!(
+        query,
+        "MATCH (n:Profile) WHERE n.name STARTS WITH $p0 AND m.name ENDS WITH $p1 \
+         OR n.name CONTAINS $p2 AND n.name =~ $p3"
+    );
+    assert_eq!(params["p0"], PropType::str("a"));
+    assert_eq!(params["p1"], PropType::str("b"));
+    assert_eq!(params["p2"], PropType::str("c"));
+    assert_eq!(params["p3"], PropType::str("d"));
+}
+
+#[test]
+fn rejected_operand() {
+    assert_eq!(
+        CompOper::StartsWith.check(&PropType::int(1)),
+        Err(OperandError {
+            op: CompOper::StartsWith,
+            value: String::from("1"),
+        })
+    );
+    assert_eq!(
+        CompOper::In
+            .check(&PropType::str("a"))
+            .unwrap_err()
+            .to_string(),
+        "operator `IN` expects a list, got 'a'"
+    );
+    assert!(CompOper::In
+        .check(&PropType::Array(vec![PropType::int(1)]))
+        .is_ok());
+}
+
+#[test]
+#[should_panic(expected = "operator `=~` expects a string, got 1")]
+fn rejected_operand_panics() {
+    matched()
+        .where_eq_str("name", "a")
+        .and("name", CompOper::Regex, PropType::int(1));
+}
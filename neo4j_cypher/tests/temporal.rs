@@ -0,0 +1,236 @@
+use std::time::Duration;
+
+use neo4j_cypher::entity::{Node, PropType, Props};
+use neo4j_cypher::query::{Query, QueryTrait};
+
+#[test]
+fn temporal_literals() {
+    let cases = [
+        (PropType::date("2024-05-01"), "date('2024-05-01')"),
+        (PropType::time("10:15:00+02:00"), "time('10:15:00+02:00')"),
+        (PropType::local_time("10:15:00"), "localtime('10:15:00')"),
+        (
+            PropType::datetime("2024-05-01T10:15:00Z"),
+            "datetime('2024-05-01T10:15:00Z')",
+        ),
+        (
+            PropType::local_datetime("2024-05-01T10:15:00"),
+            "localdatetime('2024-05-01T10:15:00')",
+        ),
+        (PropType::duration("P1DT2H"), "duration('P1DT2H')"),
+        (
+            PropType::from(Duration::from_millis(1500)),
+            "duration('PT1.5S')",
+        ),
+        (PropType::from(Duration::from_secs(90)), "duration('PT90S')"),
+    ];
+
+    for (value, literal) in cases {
+        assert_eq!(value.to_prop(), literal);
+    }
+}
+
+#[test]
+fn temporal_params_keep_their_type() {
+    let props = Props::from([(String::from("born"), PropType::date("2024-05-01"))]);
+    let node = Node::new("n", "Profile", Some(props), None);
+
+    let (query, params) = Query::init_with_params()
+        .create(vec![&node.into()])
+        .r#return("n")
+        .finalize_with_params();
+
+    assert_eq!(query, "CREATE (n:Profile { born: $p0 })\nRETURN n");
+    assert_eq!(params["p0"], PropType::Date(String::from("2024-05-01")));
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn chrono_types() {
+    use chrono::{DateTime, NaiveDate, TimeDelta};
+
+    let at = DateTime::parse_from_rfc3339("2024-05-01T10:15:30.25-03:30").unwrap();
+    let cases = [
+        (
+            PropType::from(NaiveDate::from_ymd_opt(2024, 5, 1).unwrap()),
+            "date('2024-05-01')",
+        ),
+        (PropType::from(at.time()), "localtime('10:15:30.25')"),
+        (
+            PropType::from(at.naive_local()),
+            "localdatetime('2024-05-01T10:15:30.25')",
+        ),
+        (
+            PropType::from(at),
+            "datetime('2024-05-01T10:15:30.25-03:30')",
+        ),
+        (
+            PropType::from(TimeDelta::milliseconds(-2500)),
+            "duration('-PT2.5S')",
+        ),
+    ];
+
+    for (value, literal) in cases {
+        assert_eq!(value.to_prop(), literal);
+    }
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn time_types() {
+    use time::{Date, Duration, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+    let date = Date::from_calendar_date(2024, Month::May, 1).unwrap();
+    let time = Time::from_hms_milli(10, 15, 30, 250).unwrap();
+    let offset = UtcOffset::from_hms(2, 0, 0).unwrap();
+    let cases = [
+        (PropType::from(date), "date('2024-05-01')"),
+        (PropType::from(time), "localtime('10:15:30.25')"),
+        (
+            PropType::from(PrimitiveDateTime::new(date, time)),
+            "localdatetime('2024-05-01T10:15:30.25')",
+        ),
+        (
+            PropType::from(OffsetDateTime::new_in_offset(date, time, offset)),
+            "datetime('2024-05-01T10:15:30.25+02:00')",
+        ),
+        (PropType::from(Duration::seconds(-90)), "duration('-PT90S')"),
+    ];
+
+    for (value, literal) in cases {
+        assert_eq!(value.to_prop(), literal);
+    }
+}
+
+#[cfg(feature = "derive")]
+mod derive {
+    mod own {
+        use neo4j_cypher::CypQue;
+        use std::fmt::{self, Display};
+
+        /// A user type that only shares its name with a temporal type.
+        #[derive(Clone)]
+        pub struct Duration(pub u8);
+
+        impl Display for Duration {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{} laps", self.0)
+            }
+        }
+
+        #[derive(CypQue)]
+        pub struct Race {
+            pub length: Duration,
+            pub took: std::time::Duration,
+            pub best: Option<::core::time::Duration>,
+        }
+    }
+
+    mod imported {
+        use neo4j_cypher::CypQue;
+        use std::fmt::Display;
+        use std::time::Duration;
+
+        #[derive(CypQue)]
+        pub struct Lap {
+            #[cypher(temporal)]
+            pub took: Duration,
+            #[cypher(temporal)]
+            pub splits: Vec<Duration>,
+        }
+    }
+
+    #[cfg(feature = "chrono")]
+    mod chrono_fields {
+        use chrono::NaiveDate;
+        use neo4j_cypher::CypQue;
+        use std::fmt::Display;
+
+        #[derive(CypQue)]
+        pub struct Event {
+            pub day: chrono::NaiveDate,
+            #[cypher(temporal)]
+            pub since: NaiveDate,
+            pub delay: Option<chrono::Duration>,
+        }
+    }
+
+    #[cfg(feature = "time")]
+    mod time_fields {
+        use neo4j_cypher::CypQue;
+        use std::fmt::Display;
+
+        #[derive(CypQue)]
+        pub struct Shift {
+            pub day: time::Date,
+            pub start: ::time::Time,
+        }
+    }
+
+    use neo4j_cypher::entity::NodeTrait;
+    use neo4j_cypher::query::{Query, QueryTrait};
+    use std::time::Duration;
+
+    fn create<T: NodeTrait>(item: &T) -> String {
+        Query::init()
+            .create(vec![&item.node("n").into()])
+            .finalize()
+    }
+
+    #[test]
+    fn qualified_paths_and_own_types() {
+        let race = own::Race {
+            length: own::Duration(3),
+            took: Duration::from_millis(1500),
+            best: None,
+        };
+
+        assert_eq!(
+            create(&race),
+            "CREATE (n:Race { length: '3 laps',took: duration('PT1.5S') })"
+        );
+    }
+
+    #[test]
+    fn imported_types_with_attribute() {
+        let lap = imported::Lap {
+            took: Duration::from_secs(90),
+            splits: vec![Duration::from_secs(40), Duration::from_secs(50)],
+        };
+
+        assert_eq!(
+            create(&lap),
+            "CREATE (n:Lap { took: duration('PT90S'),splits: [duration('PT40S'),duration('PT50S')] })"
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_fields() {
+        let day = chrono::NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+        let event = chrono_fields::Event {
+            day,
+            since: day,
+            delay: Some(chrono::Duration::seconds(5)),
+        };
+
+        assert_eq!(
+            create(&event),
+            "CREATE (n:Event { day: date('2024-05-01'),since: date('2024-05-01'),delay: duration('PT5S') })"
+        );
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn time_fields() {
+        let shift = time_fields::Shift {
+            day: time::Date::from_calendar_date(2024, time::Month::May, 1).unwrap(),
+            start: time::Time::from_hms(8, 30, 0).unwrap(),
+        };
+
+        assert_eq!(
+            create(&shift),
+            "CREATE (n:Shift { day: date('2024-05-01'),start: localtime('08:30:00') })"
+        );
+    }
+}
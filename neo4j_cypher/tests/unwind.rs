@@ -0,0 +1,157 @@
+use neo4j_cypher::entity::{Label, Node, NodeTrait, PropType, Props};
+use neo4j_cypher::query::Query;
+
+struct Account {
+    id: i64,
+    name: &'static str,
+    admin: bool,
+}
+
+impl NodeTrait for Account {
+    fn node(&self, nv: &str) -> Node<'_> {
+        let mut props = Props::new();
+        props.insert(String::from("id"), PropType::int(self.id));
+        props.insert(String::from("name"), PropType::str(self.name));
+
+        let mut labels: Vec<Label> = vec![Box::new("User")];
+        if self.admin {
+            labels.push(Box::new("Admin"));
+        }
+
+        Node::new(nv, "Account", Some(props), Some(labels))
+    }
+
+    fn keys(&self) -> Vec<&'static str> {
+        vec!["id"]
+    }
+}
+
+struct Tag {
+    name: &'static str,
+}
+
+impl NodeTrait for Tag {
+    fn node(&self, nv: &str) -> Node<'_> {
+        let mut props = Props::new();
+        props.insert(String::from("name"), PropType::str(self.name));
+
+        Node::new(nv, "Tag", Some(props), None)
+    }
+}
+
+fn accounts() -> Vec<Account> {
+    vec![
+        Account {
+            id: 1,
+            name: "a",
+            admin: false,
+        },
+        Account {
+            id: 2,
+            name: "b",
+            admin: false,
+        },
+    ]
+}
+
+fn row(id: i64, name: &'static str) -> PropType {
+    PropType::Map(Props::from([
+        (String::from("id"), PropType::int(id)),
+        (String::from("name"), PropType::str(name)),
+    ]))
+}
+
+#[test]
+fn create_unwind_binds_rows() {
+    let (query, params) = Query::init()
+        .create_unwind("n", &accounts())
+        .finalize_with_params();
+
+    assert_eq!(
+        query,
+        "UNWIND $rows AS row\nCREATE (n:Account:User)\nSET n = row"
+    );
+    assert_eq!(
+        params["rows"],
+        PropType::Array(vec![row(1, "a"), row(2, "b")])
+    );
+}
+
+#[test]
+fn create_unwind_with_params() {
+    let (query, params) = Query::init_with_params()
+        .create_unwind("n", &accounts())
+        .finalize_with_params();
+
+    assert_eq!(
+        query,
+        "UNWIND $rows AS row\nCREATE (n:Account:User)\nSET n = row"
+    );
+    assert_eq!(params.len(), 1);
+}
+
+#[test]
+fn merge_unwind_by_keys() {
+    let (query, params) = Query::init()
+        .merge_unwind("n", &accounts())
+        .finalize_with_params();
+
+    assert_eq!(
+        query,
+        "UNWIND $rows AS row\nMERGE (n:Account:User { id: row.id })\nSET n += row"
+    );
+    assert_eq!(
+        params["rows"],
+        PropType::Array(vec![row(1, "a"), row(2, "b")])
+    );
+}
+
+#[test]
+fn unwind_varying_labels() {
+    let mut items = accounts();
+    items[1].admin = true;
+
+    let (query, params) = Query::init()
+        .merge_unwind("n", &items)
+        .finalize_with_params();
+
+    assert_eq!(
+        query,
+        "UNWIND $rows AS row\n\
+         MERGE (n:Account:User { id: row.props.id })\n\
+         SET n += row.props\n\
+         FOREACH (i IN CASE WHEN 'Admin' IN row.labels THEN [1] ELSE [] END | SET n:Admin)"
+    );
+    let labels = |labels: &[&'static str]| {
+        PropType::Array(labels.iter().map(|label| PropType::str(*label)).collect())
+    };
+    assert_eq!(
+        params["rows"],
+        PropType::Array(vec![
+            PropType::Map(Props::from([
+                (String::from("props"), row(1, "a")),
+                (String::from("labels"), labels(&["User"])),
+            ])),
+            PropType::Map(Props::from([
+                (String::from("props"), row(2, "b")),
+                (String::from("labels"), labels(&["User", "Admin"])),
+            ])),
+        ])
+    );
+}
+
+#[test]
+fn unwind_empty_slice() {
+    let (query, params) = Query::init()
+        .create_unwind("n", &Vec::<Account>::new())
+        .finalize_with_params();
+
+    assert_eq!(query, "");
+    assert!(params.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "bulk MERGE of `Tag` nodes requires #[cypher(key)] fields")]
+fn merge_unwind_without_keys() {
+    Query::init().merge_unwind("n", &[Tag { name: "x" }]);
+}